@@ -1,9 +1,11 @@
+use crate::config::Setting;
 use crate::error::{GranolaError, Result};
 use crate::models::{Cache, CacheDocument, TranscriptSegment};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 pub fn load_cache(cache_path: &PathBuf) -> Result<Cache> {
     // Read file
@@ -32,9 +34,12 @@ pub fn load_cache(cache_path: &PathBuf) -> Result<Cache> {
     // Parse transcripts
     let transcripts = parse_transcripts(state)?;
 
+    let folders = parse_folders(state);
+
     Ok(Cache {
         documents,
         transcripts,
+        folders,
     })
 }
 
@@ -56,6 +61,34 @@ fn parse_documents(
     Ok(documents)
 }
 
+/// Folders are `documentLists` (list ID to document IDs) with their titles in
+/// `documentListsMetadata`. Lists without a title are skipped.
+fn parse_folders(state: &serde_json::Map<String, Value>) -> HashMap<String, Vec<String>> {
+    let mut folders: HashMap<String, Vec<String>> = HashMap::new();
+
+    let Some(lists) = state.get("documentLists").and_then(Value::as_object) else {
+        return folders;
+    };
+    for (list_id, doc_ids) in lists {
+        let Some(title) = state
+            .get("documentListsMetadata")
+            .and_then(|m| m[list_id]["title"].as_str())
+        else {
+            continue;
+        };
+        for doc_id in doc_ids.as_array().into_iter().flatten() {
+            // Entries are usually bare IDs, but may be document objects
+            if let Some(doc_id) = doc_id.as_str().or_else(|| doc_id["id"].as_str()) {
+                folders
+                    .entry(doc_id.to_string())
+                    .or_default()
+                    .push(title.to_string());
+            }
+        }
+    }
+    folders
+}
+
 fn parse_transcripts(
     state: &serde_json::Map<String, Value>,
 ) -> Result<HashMap<String, Vec<TranscriptSegment>>> {
@@ -80,17 +113,61 @@ fn parse_transcripts(
     Ok(transcripts)
 }
 
-pub fn resolve_cache_path(cli_path: Option<PathBuf>) -> PathBuf {
-    // Priority: CLI flag > env var > default
-    if let Some(path) = cli_path {
-        return path;
+pub fn resolve_cache_path(
+    cli_path: Option<PathBuf>,
+    configured: Option<(PathBuf, String)>,
+) -> Setting<PathBuf> {
+    // Priority: CLI flag > env var > config file > default
+    let env_path = std::env::var("GRANOLA_CACHE_PATH")
+        .ok()
+        .map(|path| (PathBuf::from(path), "GRANOLA_CACHE_PATH"));
+
+    // Default path
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let default = PathBuf::from(home).join("Library/Application Support/Granola/cache-v3.json");
+
+    Setting::resolve(cli_path, env_path, configured, default)
+}
+
+/// Last modification time of the cache file, if it can be read.
+pub fn cache_modified(cache_path: &Path) -> Option<SystemTime> {
+    fs::metadata(cache_path).and_then(|m| m.modified()).ok()
+}
+
+/// A loaded cache that long-running commands refresh when the file changes.
+pub struct LiveCache {
+    path: PathBuf,
+    cache: Cache,
+    mtime: Option<SystemTime>,
+}
+
+impl LiveCache {
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let mtime = cache_modified(&path);
+        let cache = load_cache(&path)?;
+        Ok(LiveCache { path, cache, mtime })
     }
 
-    if let Ok(env_path) = std::env::var("GRANOLA_CACHE_PATH") {
-        return PathBuf::from(env_path);
+    pub fn cache(&self) -> &Cache {
+        &self.cache
     }
 
-    // Default path
-    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(home).join("Library/Application Support/Granola/cache-v3.json")
+    /// Reload the cache if its mtime changed since it was last loaded.
+    pub fn refresh(&mut self) {
+        let mtime = cache_modified(&self.path);
+        if mtime == self.mtime {
+            return;
+        }
+
+        // Granola may be mid-write; keep the old data until the file parses
+        // again
+        match load_cache(&self.path) {
+            Ok(cache) => {
+                self.cache = cache;
+                self.mtime = mtime;
+                eprintln!("Reloaded {}", self.path.display());
+            }
+            Err(e) => eprintln!("Keeping previous cache, reload failed: {}", e),
+        }
+    }
 }
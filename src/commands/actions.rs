@@ -0,0 +1,314 @@
+use crate::dates::parse_timestamp;
+use crate::error::Result;
+use crate::models::{ActionItem, ActionsOutput, Cache, CacheDocument};
+use crate::speakers::SpeakerMapping;
+use chrono::{DateTime, Utc};
+
+/// Headings whose list items are all action items
+const ACTION_HEADINGS: &[&str] = &[
+    "action items",
+    "actions",
+    "next steps",
+    "todo",
+    "to do",
+    "to-do",
+    "follow-ups",
+    "follow ups",
+    "follow up",
+];
+
+/// Line prefixes that mark a single action item
+const ACTION_PREFIXES: &[&str] = &["todo:", "todo ", "action item:", "action:", "ai:"];
+
+/// Phrases in speech that usually mean someone is committing to something
+const COMMITMENT_PHRASES: &[&str] = &[
+    "i'll ",
+    "i will ",
+    "we'll ",
+    "we will ",
+    "let me ",
+    "action item",
+    "follow up on",
+];
+
+/// Leading words that look like a name in "X to ..." but aren't one
+const NOT_NAMES: &[&str] = &[
+    "i", "we", "you", "they", "someone", "everyone", "team", "need",
+];
+
+#[derive(Debug, Default)]
+pub struct ActionOptions {
+    /// Only include meetings created at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only include items whose assignee matches (case-insensitive substring)
+    pub owner: Option<String>,
+    /// Only include items that are not completed
+    pub open_only: bool,
+    /// Also look for commitments in transcripts
+    pub include_transcripts: bool,
+}
+
+pub fn find_action_items(options: &ActionOptions, cache: &Cache) -> Result<ActionsOutput> {
+    let mut docs: Vec<&CacheDocument> = cache
+        .documents
+        .values()
+        .filter(|doc| match options.since {
+            Some(since) => parse_timestamp(&doc.created_at).is_some_and(|d| d >= since),
+            None => true,
+        })
+        .collect();
+
+    // Newest meetings first, like search
+    docs.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+
+    let mut items = Vec::new();
+    for doc in docs {
+        let participants = participant_names(doc);
+
+        if let Some(notes) = notes_text(doc) {
+            items.extend(extract_from_text(doc, &notes, "notes", &participants));
+        }
+        if let Some(overview) = &doc.overview {
+            items.extend(extract_from_text(doc, overview, "overview", &participants));
+        }
+        if options.include_transcripts {
+            items.extend(extract_from_transcript(doc, cache, &participants));
+        }
+    }
+
+    items.retain(|item| {
+        let owner_matches = match &options.owner {
+            Some(owner) => item
+                .assignee
+                .as_ref()
+                .is_some_and(|a| a.to_lowercase().contains(&owner.to_lowercase())),
+            None => true,
+        };
+        owner_matches && !(options.open_only && item.completed)
+    });
+
+    Ok(ActionsOutput {
+        total_items: items.len(),
+        items,
+    })
+}
+
+/// Markdown keeps checkboxes and headings, so prefer it over plain notes.
+fn notes_text(doc: &CacheDocument) -> Option<String> {
+    doc.notes_markdown
+        .clone()
+        .or_else(|| doc.structured_notes().map(|n| n.to_markdown()))
+        .or_else(|| doc.notes_plain.clone())
+}
+
+fn extract_from_text(
+    doc: &CacheDocument,
+    text: &str,
+    source: &str,
+    participants: &[String],
+) -> Vec<ActionItem> {
+    let mut items = Vec::new();
+    let mut in_action_section = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        // `#tag` lines are text, only `# Heading` is a heading
+        let hashes = trimmed.trim_start_matches('#');
+        if hashes.len() < trimmed.len() && (hashes.is_empty() || hashes.starts_with(' ')) {
+            let heading = hashes.trim().trim_end_matches(':');
+            in_action_section = ACTION_HEADINGS.contains(&heading.to_lowercase().as_str());
+            continue;
+        }
+
+        let (is_list_item, body) = strip_list_marker(trimmed);
+        let (checkbox, body) = strip_checkbox(body);
+        let (has_prefix, body) = strip_action_prefix(body);
+
+        let is_action = checkbox.is_some() || has_prefix || (in_action_section && is_list_item);
+        if !is_action || body.is_empty() {
+            continue;
+        }
+
+        items.push(ActionItem {
+            meeting_id: doc.id.clone(),
+            meeting_title: doc.title.clone(),
+            date: doc.created_at.clone(),
+            text: body.to_string(),
+            assignee: guess_assignee(body, participants),
+            completed: checkbox.unwrap_or(false),
+            source: source.to_string(),
+            timestamp: None,
+        });
+    }
+
+    items
+}
+
+fn extract_from_transcript(
+    doc: &CacheDocument,
+    cache: &Cache,
+    participants: &[String],
+) -> Vec<ActionItem> {
+    let Some(segments) = cache.transcripts.get(&doc.id) else {
+        return Vec::new();
+    };
+
+    // The speaker is the best guess for who committed to something
+    let labels = SpeakerMapping::from_participants().labels_for(doc);
+
+    segments
+        .iter()
+        .filter(|s| {
+            let text = s.text.to_lowercase();
+            COMMITMENT_PHRASES.iter().any(|p| text.contains(p))
+        })
+        .map(|s| ActionItem {
+            meeting_id: doc.id.clone(),
+            meeting_title: doc.title.clone(),
+            date: doc.created_at.clone(),
+            text: s.text.trim().to_string(),
+            assignee: guess_assignee(&s.text, participants).or_else(|| {
+                let label = labels.label(&s.source);
+                // Raw sources are not names
+                (label != s.source).then_some(label)
+            }),
+            completed: false,
+            source: "transcript".to_string(),
+            timestamp: Some(s.timestamp),
+        })
+        .collect()
+}
+
+fn strip_list_marker(line: &str) -> (bool, &str) {
+    for marker in ["- ", "* ", "+ ", "• "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return (true, rest.trim_start());
+        }
+    }
+
+    // Numbered items: "1. " or "1) "
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return (true, rest.trim_start());
+        }
+    }
+
+    (false, line)
+}
+
+fn strip_checkbox(line: &str) -> (Option<bool>, &str) {
+    for (prefix, checked) in [("[ ]", false), ("[x]", true), ("[X]", true)] {
+        if let Some(rest) = line.strip_prefix(prefix) {
+            return (Some(checked), rest.trim_start());
+        }
+    }
+    (None, line)
+}
+
+fn strip_action_prefix(line: &str) -> (bool, &str) {
+    for prefix in ACTION_PREFIXES {
+        if line
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        {
+            return (true, line[prefix.len()..].trim_start());
+        }
+    }
+    (false, line)
+}
+
+/// Guess who owns an action item from `@mentions`, a leading
+/// "Name to ..." / "Name will ...", or any participant named in the text.
+fn guess_assignee(text: &str, participants: &[String]) -> Option<String> {
+    if let Some(mention) = text.split_whitespace().find_map(|w| w.strip_prefix('@')) {
+        let mention = mention.trim_end_matches(|c: char| !c.is_alphanumeric());
+        if !mention.is_empty() {
+            return Some(resolve_participant(mention, participants).unwrap_or(mention.to_string()));
+        }
+    }
+
+    let lower = text.to_lowercase();
+    // "Name: do something" is only trusted for people in the meeting, since
+    // notes often use "Label: detail"
+    for (separator, participants_only) in [(" to ", false), (" will ", false), (": ", true)] {
+        // Search the original text: lowercasing can change byte lengths, so
+        // offsets into `lower` don't line up with `text`
+        if let Some(pos) = find_ignore_ascii_case(text, separator) {
+            let candidate = text[..pos].trim();
+            // Only short capitalised leading phrases are plausibly a name,
+            // which may belong to someone who wasn't in the meeting
+            let looks_like_name = !candidate.is_empty()
+                && candidate.split_whitespace().count() <= 2
+                && candidate
+                    .split_whitespace()
+                    .all(|w| w.chars().next().is_some_and(char::is_uppercase))
+                && !NOT_NAMES.contains(&candidate.to_lowercase().as_str());
+            if looks_like_name {
+                match resolve_participant(candidate, participants) {
+                    Some(name) => return Some(name),
+                    None if !participants_only => return Some(candidate.to_string()),
+                    None => {}
+                }
+            }
+        }
+    }
+
+    participants
+        .iter()
+        .find(|name| mentions(&lower, name))
+        .cloned()
+}
+
+/// Byte offset of an ASCII `needle` in `haystack`, ignoring ASCII case.
+/// ASCII bytes never occur inside a multi-byte character, so the offset is
+/// always a char boundary.
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Match a name against the meeting's participants by full or first name.
+fn resolve_participant(name: &str, participants: &[String]) -> Option<String> {
+    let name = name.to_lowercase();
+    participants
+        .iter()
+        .find(|p| {
+            let p = p.to_lowercase();
+            p == name || p.split_whitespace().next() == Some(name.as_str())
+        })
+        .cloned()
+}
+
+fn mentions(lower_text: &str, participant: &str) -> bool {
+    let participant = participant.to_lowercase();
+    let first = participant.split_whitespace().next().unwrap_or("");
+    lower_text
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| !word.is_empty() && word == first)
+        || lower_text.contains(&participant)
+}
+
+fn participant_names(doc: &CacheDocument) -> Vec<String> {
+    let mut names = Vec::new();
+
+    if let Some(people) = &doc.people {
+        if let Some(creator) = &people.creator {
+            names.push(creator.name.clone());
+        }
+        for attendee in people.attendees.iter().flatten() {
+            if !names.contains(&attendee.name) {
+                names.push(attendee.name.clone());
+            }
+        }
+    }
+
+    names
+}
@@ -21,7 +21,7 @@ pub fn get_meeting_details(meeting_id: &str, cache: &Cache) -> Result<MeetingDet
             .clone()
             .unwrap_or_else(|| "unknown".to_string()),
         has_transcript: cache.transcripts.contains_key(&doc.id),
-        has_notes: doc.notes_plain.is_some() || doc.notes_markdown.is_some(),
+        has_notes: doc.has_notes(),
         created_at: doc.created_at.clone(),
         updated_at: doc.updated_at.clone(),
     })
@@ -1,33 +1,51 @@
 use crate::error::{GranolaError, Result};
-use crate::models::{Cache, Document, DocumentsOutput};
+use crate::models::{
+    Cache, CacheDocument, Document, DocumentsOutput, NotesOutline, OutlineHeading,
+};
+use crate::notes::{markdown_headings, markdown_section};
+use clap::ValueEnum;
+use schemars::JsonSchema;
+use serde::Deserialize;
 
-pub fn get_documents(meeting_id: &str, cache: &Cache) -> Result<DocumentsOutput> {
-    let doc = cache
-        .documents
-        .get(meeting_id)
-        .ok_or_else(|| GranolaError::MeetingNotFound(meeting_id.to_string()))?;
+/// Which representation of the meeting notes to return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NotesFormat {
+    /// Plain text, falling back to markdown
+    Plain,
+    /// Markdown, falling back to plain text
+    Markdown,
+    /// Both formats as separate documents
+    Both,
+    /// Markdown when it has structure (headings, lists, ...), otherwise plain
+    Best,
+}
+
+pub fn get_documents(
+    meeting_id: &str,
+    notes_format: NotesFormat,
+    cache: &Cache,
+) -> Result<DocumentsOutput> {
+    let doc = find_document(meeting_id, cache)?;
 
     let mut documents = Vec::new();
 
-    // Priority: notes_plain > notes_markdown
-    if let Some(content) = &doc.notes_plain {
-        documents.push(Document {
-            id: format!("{}-notes", doc.id),
-            title: format!("{} - Notes", doc.title),
-            doc_type: "meeting_notes".to_string(),
-            format: "plain".to_string(),
-            content: content.clone(),
-            word_count: content.split_whitespace().count(),
-            created_at: doc.created_at.clone(),
-        });
-    } else if let Some(content) = &doc.notes_markdown {
+    let notes = select_notes(doc, notes_format);
+    let multiple = notes.len() > 1;
+    for (format, content) in notes {
+        // Keep the plain `-notes` ID unless both formats are returned
+        let id = if multiple {
+            format!("{}-notes-{}", doc.id, format)
+        } else {
+            format!("{}-notes", doc.id)
+        };
         documents.push(Document {
-            id: format!("{}-notes", doc.id),
+            id,
             title: format!("{} - Notes", doc.title),
             doc_type: "meeting_notes".to_string(),
-            format: "markdown".to_string(),
-            content: content.clone(),
+            format: format.to_string(),
             word_count: content.split_whitespace().count(),
+            content,
             created_at: doc.created_at.clone(),
         });
     }
@@ -52,3 +70,131 @@ pub fn get_documents(meeting_id: &str, cache: &Cache) -> Result<DocumentsOutput>
         documents,
     })
 }
+
+/// The heading tree of the meeting's notes.
+pub fn get_notes_outline(meeting_id: &str, cache: &Cache) -> Result<NotesOutline> {
+    let doc = find_document(meeting_id, cache)?;
+    let markdown = markdown_notes(doc).unwrap_or_default();
+
+    // Headings stay on the stack until a heading of the same or a higher
+    // level closes them, at which point they move into their parent
+    let mut stack: Vec<OutlineHeading> = Vec::new();
+    let mut headings = Vec::new();
+    for heading in markdown_headings(&markdown) {
+        close_headings(&mut stack, &mut headings, heading.level);
+        stack.push(OutlineHeading {
+            level: heading.level,
+            title: heading.title,
+            children: Vec::new(),
+        });
+    }
+    close_headings(&mut stack, &mut headings, 0);
+
+    Ok(NotesOutline {
+        id: doc.id.clone(),
+        title: doc.title.clone(),
+        headings,
+    })
+}
+
+fn close_headings(stack: &mut Vec<OutlineHeading>, roots: &mut Vec<OutlineHeading>, level: usize) {
+    while stack.last().is_some_and(|h| h.level >= level) {
+        let heading = stack.pop().unwrap();
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(heading),
+            None => roots.push(heading),
+        }
+    }
+}
+
+/// One section of the meeting's notes, as a single Markdown document.
+pub fn get_notes_section(
+    meeting_id: &str,
+    section: &str,
+    cache: &Cache,
+) -> Result<DocumentsOutput> {
+    let doc = find_document(meeting_id, cache)?;
+    let (heading, content) = markdown_notes(doc)
+        .and_then(|markdown| markdown_section(&markdown, section))
+        .ok_or_else(|| GranolaError::SectionNotFound(doc.id.clone(), section.to_string()))?;
+
+    let document = Document {
+        id: format!("{}-notes", doc.id),
+        title: format!("{} - {}", doc.title, heading),
+        doc_type: "meeting_notes".to_string(),
+        format: "markdown".to_string(),
+        word_count: content.split_whitespace().count(),
+        content,
+        created_at: doc.created_at.clone(),
+    };
+
+    Ok(DocumentsOutput {
+        id: doc.id.clone(),
+        title: doc.title.clone(),
+        total_documents: 1,
+        documents: vec![document],
+    })
+}
+
+fn find_document<'a>(meeting_id: &str, cache: &'a Cache) -> Result<&'a CacheDocument> {
+    cache
+        .documents
+        .get(meeting_id)
+        .ok_or_else(|| GranolaError::MeetingNotFound(meeting_id.to_string()))
+}
+
+/// Markdown notes, rendered from the structured notes when not stored.
+fn markdown_notes(doc: &CacheDocument) -> Option<String> {
+    doc.notes_markdown
+        .clone()
+        .or_else(|| doc.structured_notes().map(|n| n.to_markdown()))
+}
+
+/// Pick the notes in the requested format, rendering the structured notes
+/// when Granola has not stored that format. The returned format names what the
+/// content actually is, which differs from the request after a fallback.
+fn select_notes(doc: &CacheDocument, requested: NotesFormat) -> Vec<(&'static str, String)> {
+    let structured = doc.structured_notes();
+
+    let plain = || {
+        doc.notes_plain
+            .clone()
+            .or_else(|| structured.as_ref().map(|n| n.to_plain()))
+            .map(|content| ("plain", content))
+    };
+    let markdown = || {
+        doc.notes_markdown
+            .clone()
+            .or_else(|| structured.as_ref().map(|n| n.to_markdown()))
+            .map(|content| ("markdown", content))
+    };
+
+    match requested {
+        NotesFormat::Plain => plain().or_else(markdown).into_iter().collect(),
+        NotesFormat::Markdown => markdown().or_else(plain).into_iter().collect(),
+        NotesFormat::Both => plain().into_iter().chain(markdown()).collect(),
+        NotesFormat::Best => match markdown() {
+            Some((format, content)) if has_markdown_structure(&content) => {
+                vec![(format, content)]
+            }
+            md => plain().or(md).into_iter().collect(),
+        },
+    }
+}
+
+/// Whether markdown notes carry structure that plain text would lose.
+fn has_markdown_structure(content: &str) -> bool {
+    content.contains("**")
+        || content.contains("](")
+        || content.lines().any(|line| {
+            let line = line.trim_start();
+            line.starts_with('#')
+                || line.starts_with("- ")
+                || line.starts_with("* ")
+                || line.starts_with("> ")
+                || line.starts_with("```")
+                || line
+                    .split_once(". ")
+                    .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        })
+}
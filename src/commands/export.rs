@@ -0,0 +1,594 @@
+use crate::commands::documents::NotesFormat;
+use crate::commands::search::matches_query;
+use crate::commands::transcript::TranscriptOptions;
+use crate::commands::{get_documents, get_meeting_details, get_transcript};
+use crate::dates::parse_timestamp;
+use crate::error::{GranolaError, Result};
+use crate::models::{Cache, CacheDocument, ExportOutput, ExportedFile, MeetingDetails};
+use crate::notes::markdown_headings;
+use crate::render::format_timestamp;
+use crate::speakers::SpeakerMapping;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_FILENAME: &str = "{date}-{slug}.md";
+
+/// Obsidian links notes by file name, so keep the title readable
+pub const OBSIDIAN_FILENAME: &str = "{date} {title}.md";
+
+/// Vault folders for `--obsidian`
+const MEETINGS_DIR: &str = "Meetings";
+const PEOPLE_DIR: &str = "People";
+
+/// Around the generated list of meetings in a person note
+const MEETINGS_START: &str = "<!-- granola:meetings -->";
+const MEETINGS_END: &str = "<!-- /granola:meetings -->";
+
+/// Records what earlier exports wrote, in the export directory; the leading
+/// dot keeps it out of Obsidian
+const MANIFEST_FILE: &str = ".granola-export.json";
+
+/// Placeholders a filename template may use
+const PLACEHOLDERS: &[&str] = &["id", "title", "slug", "date", "year", "month", "type"];
+
+/// Which meetings to export.
+#[derive(Debug, Default)]
+pub struct ExportFilters {
+    /// Only include meetings created at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only include meetings created before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Only include meetings matching this search query
+    pub query: Option<String>,
+}
+
+impl ExportFilters {
+    /// Meetings passing the filters, oldest first so exports follow date order.
+    pub fn select<'a>(&self, cache: &'a Cache) -> Vec<&'a CacheDocument> {
+        let query = self.query.as_deref().map(str::to_lowercase);
+        let mut docs: Vec<&CacheDocument> = cache
+            .documents
+            .values()
+            .filter(|doc| {
+                let created = parse_timestamp(&doc.created_at);
+                let after_since = self
+                    .since
+                    .is_none_or(|since| created.is_some_and(|d| d >= since));
+                let before_until = self
+                    .until
+                    .is_none_or(|until| created.is_some_and(|d| d < until));
+                let matches = query.as_deref().is_none_or(|q| matches_query(doc, q));
+                after_since && before_until && matches
+            })
+            .collect();
+        docs.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        docs
+    }
+}
+
+#[derive(Debug)]
+pub struct ExportOptions {
+    pub dir: PathBuf,
+    /// Path of each file under `dir`, e.g. `{year}/{date}-{slug}.md`;
+    /// defaults to `DEFAULT_FILENAME` or `OBSIDIAN_FILENAME`
+    pub filename: Option<String>,
+    pub filters: ExportFilters,
+    pub include_transcript: bool,
+    /// Leave files alone when the meeting's `updated_at` and transcript
+    /// length haven't changed
+    pub incremental: bool,
+    /// Lay the directory out as an Obsidian vault, with person notes and
+    /// wikilinks
+    pub obsidian: bool,
+}
+
+/// Write one Markdown file per meeting into `options.dir`, plus a note per
+/// person for Obsidian.
+pub fn export_markdown(options: &ExportOptions, cache: &Cache) -> Result<ExportOutput> {
+    let default_filename = if options.obsidian {
+        OBSIDIAN_FILENAME
+    } else {
+        DEFAULT_FILENAME
+    };
+    let template = options.filename.as_deref().unwrap_or(default_filename);
+    check_template(template)?;
+
+    let docs = options.filters.select(cache);
+    let mut manifest = Manifest::load(&options.dir);
+    // Which meeting each file belongs to, including meetings exported
+    // earlier but not selected now
+    let mut owners: HashMap<String, String> = manifest
+        .meetings
+        .iter()
+        .map(|(id, entry)| (entry.path.clone(), id.clone()))
+        .collect();
+    let mut files = Vec::new();
+    // Files left behind by meetings whose file name changed
+    let mut stale = Vec::new();
+
+    for doc in docs {
+        let details = get_meeting_details(&doc.id, cache)?;
+        let mut relative = render_filename(template, doc, &details, options.obsidian);
+        if options.obsidian {
+            relative = format!("{}/{}", MEETINGS_DIR, relative);
+        }
+        // Two meetings with the same title on the same day
+        if owners.get(&relative).is_some_and(|owner| *owner != doc.id) {
+            relative = with_suffix(&relative, &doc.id);
+        }
+        let path = options.dir.join(&relative);
+
+        let entry = ManifestEntry {
+            path: relative.clone(),
+            updated_at: doc.updated_at.clone(),
+            transcript_segments: cache.transcripts.get(&doc.id).map_or(0, Vec::len),
+            transcript: options.include_transcript,
+            title: details.title.clone(),
+            date: details.date.clone(),
+            participants: details
+                .participants
+                .iter()
+                .map(|p| Participant {
+                    name: p.name.clone(),
+                    email: p.email.clone(),
+                })
+                .collect(),
+        };
+        let previous = manifest.meetings.get(&doc.id);
+        let unchanged = options.incremental && previous == Some(&entry) && path.exists();
+        if let Some(previous) = previous.filter(|p| p.path != relative) {
+            owners.remove(&previous.path);
+            stale.push(previous.path.clone());
+        }
+        owners.insert(relative.clone(), doc.id.clone());
+        manifest.meetings.insert(doc.id.clone(), entry);
+        if !unchanged {
+            let content = meeting_markdown(doc, &details, options, cache)?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)?;
+        }
+
+        files.push(ExportedFile {
+            meeting_id: doc.id.clone(),
+            path: Some(relative),
+            status: if unchanged { "unchanged" } else { "written" }.to_string(),
+        });
+    }
+
+    let mut removed_files = Vec::new();
+    for relative in stale {
+        // Another meeting may have taken the name since
+        if manifest.meetings.values().any(|e| e.path == relative) {
+            continue;
+        }
+        match std::fs::remove_file(options.dir.join(&relative)) {
+            Ok(()) => removed_files.push(relative),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    manifest.save(&options.dir)?;
+
+    // Built from every meeting exported so far, so a filtered export keeps
+    // the links to earlier ones
+    let people_files = if options.obsidian {
+        People::from_manifest(&manifest).write(&options.dir)?
+    } else {
+        Vec::new()
+    };
+
+    let written = files.iter().filter(|f| f.status == "written").count();
+    Ok(ExportOutput {
+        destination: options.dir.display().to_string(),
+        total_meetings: files.len(),
+        written,
+        unchanged: files.len() - written,
+        files,
+        removed_files,
+        people_files,
+    })
+}
+
+/// What was exported for each meeting, so later runs can tell what changed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    meetings: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ManifestEntry {
+    /// Relative to the export directory
+    path: String,
+    updated_at: String,
+    transcript_segments: usize,
+    /// Whether the file includes the transcript
+    transcript: bool,
+    /// For the person notes of `--obsidian`
+    title: String,
+    date: String,
+    participants: Vec<Participant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Participant {
+    name: String,
+    email: Option<String>,
+}
+
+impl Manifest {
+    /// An unreadable manifest just means every meeting is written again.
+    fn load(dir: &Path) -> Manifest {
+        std::fs::read_to_string(dir.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let content = serde_json::to_string_pretty(self).expect("manifest always serializes");
+        std::fs::write(dir.join(MANIFEST_FILE), content)?;
+        Ok(())
+    }
+}
+
+/// The people met in the exported meetings, for their Obsidian notes.
+#[derive(Default)]
+struct People {
+    /// Keyed by note name, which is also what meeting notes link to
+    people: BTreeMap<String, Person>,
+}
+
+struct Person {
+    name: String,
+    email: Option<String>,
+    /// Daily note, vault path (without `.md`) and title of each meeting,
+    /// oldest first
+    meetings: Vec<(String, String, String)>,
+}
+
+impl People {
+    fn from_manifest(manifest: &Manifest) -> People {
+        let mut entries: Vec<&ManifestEntry> = manifest.meetings.values().collect();
+        entries.sort_by(|a, b| a.date.cmp(&b.date).then(a.path.cmp(&b.path)));
+
+        let mut people = People::default();
+        for entry in entries {
+            let link = entry.path.strip_suffix(".md").unwrap_or(&entry.path);
+            for participant in &entry.participants {
+                let person = people
+                    .people
+                    .entry(note_name(&participant.name))
+                    .or_insert_with(|| Person {
+                        name: participant.name.clone(),
+                        email: None,
+                        meetings: Vec::new(),
+                    });
+                if person.email.is_none() {
+                    person.email = participant.email.clone();
+                }
+                person.meetings.push((
+                    daily_note(&entry.date),
+                    link.to_string(),
+                    entry.title.clone(),
+                ));
+            }
+        }
+        people
+    }
+
+    /// Write the notes that changed and return their paths. Only the list of
+    /// meetings between the markers is ours; anything else the user wrote in
+    /// an existing note is kept.
+    fn write(&self, dir: &Path) -> Result<Vec<String>> {
+        let mut written = Vec::new();
+        for (note, person) in &self.people {
+            let mut section = format!("{}\n", MEETINGS_START);
+            for (date, link, title) in &person.meetings {
+                let _ = writeln!(section, "- [[{}]] {}", date, wikilink(link, title));
+            }
+            section.push_str(MEETINGS_END);
+
+            let relative = format!("{}/{}.md", PEOPLE_DIR, note);
+            let path = dir.join(&relative);
+            let existing = std::fs::read_to_string(&path).ok();
+            let content = match &existing {
+                Some(existing) => replace_section(existing, &section),
+                None => {
+                    let mut content = String::from("---\n");
+                    let _ = writeln!(content, "name: {}", yaml_string(&person.name));
+                    if let Some(email) = &person.email {
+                        let _ = writeln!(content, "email: {}", yaml_string(email));
+                    }
+                    content.push_str("tags:\n  - \"person\"\n---\n\n");
+                    let _ = writeln!(content, "# {}\n\n## Meetings\n\n{}", person.name, section);
+                    content
+                }
+            };
+
+            // Leave untouched notes alone so Obsidian doesn't see churn
+            if existing.as_deref() == Some(content.as_str()) {
+                continue;
+            }
+            std::fs::create_dir_all(dir.join(PEOPLE_DIR))?;
+            std::fs::write(&path, content)?;
+            written.push(relative);
+        }
+        Ok(written)
+    }
+}
+
+/// Swap the marked section of a person note for `section`, or add it at the
+/// end if the markers were removed.
+fn replace_section(note: &str, section: &str) -> String {
+    let bounds = note.find(MEETINGS_START).and_then(|start| {
+        let end = note[start..].find(MEETINGS_END)? + start + MEETINGS_END.len();
+        Some((start, end))
+    });
+    match bounds {
+        Some((start, end)) => format!("{}{}{}", &note[..start], section, &note[end..]),
+        None => format!("{}\n## Meetings\n\n{}\n", note.trim_end(), section),
+    }
+}
+
+fn meeting_markdown(
+    doc: &CacheDocument,
+    details: &MeetingDetails,
+    options: &ExportOptions,
+    cache: &Cache,
+) -> Result<String> {
+    let mut out = String::new();
+
+    out.push_str("---\n");
+    let _ = writeln!(out, "id: {}", yaml_string(&details.id));
+    let _ = writeln!(out, "title: {}", yaml_string(&details.title));
+    let _ = writeln!(out, "date: {}", yaml_string(&details.date));
+    if details.participants.is_empty() {
+        out.push_str("participants: []\n");
+    } else {
+        out.push_str("participants:\n");
+        for participant in &details.participants {
+            let _ = writeln!(out, "  - {}", yaml_string(&participant.name));
+        }
+    }
+    let _ = writeln!(out, "type: {}", yaml_string(&details.meeting_type));
+    if options.obsidian {
+        let tags = meeting_tags(details, cache);
+        if !tags.is_empty() {
+            out.push_str("tags:\n");
+            for tag in tags {
+                let _ = writeln!(out, "  - {}", yaml_string(&tag));
+            }
+        }
+    }
+    let _ = writeln!(out, "updated_at: {}", yaml_string(&doc.updated_at));
+    out.push_str("---\n\n");
+
+    let _ = writeln!(out, "# {}", details.title);
+
+    if options.obsidian {
+        let _ = writeln!(out, "\nDate: [[{}]]", daily_note(&details.date));
+        let links: Vec<String> = details
+            .participants
+            .iter()
+            .map(|p| wikilink(&note_name(&p.name), &p.name))
+            .collect();
+        if !links.is_empty() {
+            let _ = writeln!(out, "Participants: {}", links.join(", "));
+        }
+    }
+
+    let documents = get_documents(&doc.id, NotesFormat::Markdown, cache)?;
+    for (doc_type, heading) in [("overview", "Overview"), ("meeting_notes", "Notes")] {
+        let content = documents
+            .documents
+            .iter()
+            .find(|d| d.doc_type == doc_type)
+            .map(|d| d.content.trim())
+            .filter(|c| !c.is_empty());
+        if let Some(content) = content {
+            let _ = write!(out, "\n## {}\n\n{}\n", heading, nest_headings(content, 3));
+        }
+    }
+
+    if options.include_transcript {
+        let options = TranscriptOptions {
+            merge_turns: true,
+            speaker_mapping: SpeakerMapping::from_participants(),
+            ..Default::default()
+        };
+        // Meetings without a transcript just leave the section out
+        if let Ok(transcript) = get_transcript(&doc.id, &options, cache) {
+            out.push_str("\n## Transcript\n");
+            for segment in &transcript.segments {
+                let _ = write!(
+                    out,
+                    "\n**{}** [{}]: {}\n",
+                    segment.s,
+                    format_timestamp(segment.ts),
+                    segment.t
+                );
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Shift the headings in `markdown` so the shallowest is at `level`, keeping
+/// the notes' own headings below the section they are exported under.
+pub fn nest_headings(markdown: &str, level: usize) -> String {
+    let headings = markdown_headings(markdown);
+    let Some(shallowest) = headings.iter().map(|h| h.level).min() else {
+        return markdown.to_string();
+    };
+    let shift = level.saturating_sub(shallowest);
+    if shift == 0 {
+        return markdown.to_string();
+    }
+
+    let mut lines: Vec<String> = markdown.lines().map(str::to_string).collect();
+    for heading in headings {
+        let new_level = (heading.level + shift).min(6);
+        lines[heading.line] = format!("{} {}", "#".repeat(new_level), heading.title);
+    }
+    lines.join("\n")
+}
+
+/// `type/<type>` and `folder/<folder>` tags, which Obsidian shows nested.
+fn meeting_tags(details: &MeetingDetails, cache: &Cache) -> Vec<String> {
+    let mut tags = Vec::new();
+    if details.meeting_type != "unknown" {
+        tags.push(format!("type/{}", slugify(&details.meeting_type)));
+    }
+    for folder in cache.folders.get(&details.id).into_iter().flatten() {
+        tags.push(format!("folder/{}", slugify(folder)));
+    }
+    tags
+}
+
+/// `[[target]]`, or `[[target|text]]` when the text differs.
+fn wikilink(target: &str, text: &str) -> String {
+    if target == text {
+        format!("[[{}]]", target)
+    } else {
+        format!("[[{}|{}]]", target, text)
+    }
+}
+
+/// Name of the daily note for a meeting, in Obsidian's default `YYYY-MM-DD`.
+fn daily_note(date: &str) -> String {
+    parse_timestamp(date)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| date.chars().take(10).collect())
+}
+
+/// A file name that also works as a wikilink target: Obsidian doesn't allow
+/// `#`, `^`, `[`, `]` or `|` in links.
+fn note_name(value: &str) -> String {
+    sanitize(value).replace(['#', '^', '[', ']', '|'], "-")
+}
+
+/// JSON strings are valid YAML double-quoted scalars, and quoting everything
+/// keeps titles like `Q3: plans` or `yes` from being misread.
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+fn check_template(template: &str) -> Result<()> {
+    let escapes = Path::new(template).is_absolute()
+        || Path::new(template)
+            .components()
+            .any(|c| c == std::path::Component::ParentDir);
+    if escapes {
+        return Err(GranolaError::InvalidArguments(format!(
+            "Filename template '{}' must stay inside the export directory",
+            template
+        )));
+    }
+
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}').ok_or_else(|| {
+            GranolaError::InvalidArguments(format!(
+                "Unclosed '{{' in filename template '{}'",
+                template
+            ))
+        })?;
+        let name = &rest[start + 1..start + end];
+        if !PLACEHOLDERS.contains(&name) {
+            return Err(GranolaError::InvalidArguments(format!(
+                "Unknown placeholder '{{{}}}' in filename template; use one of: {}",
+                name,
+                PLACEHOLDERS.join(", ")
+            )));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    Ok(())
+}
+
+/// Fill in a checked template. Values never contain path separators, so only
+/// the template itself can create subdirectories.
+fn render_filename(
+    template: &str,
+    doc: &CacheDocument,
+    details: &MeetingDetails,
+    obsidian: bool,
+) -> String {
+    let created = parse_timestamp(&doc.created_at);
+    let date_part = |format: &str| {
+        created
+            .map(|d| d.format(format).to_string())
+            .unwrap_or_else(|| "undated".to_string())
+    };
+
+    let mut name = template.to_string();
+    for placeholder in PLACEHOLDERS {
+        let value = match *placeholder {
+            "id" => doc.id.clone(),
+            "title" => doc.title.clone(),
+            "slug" => slugify(&doc.title),
+            "date" => date_part("%Y-%m-%d"),
+            "year" => date_part("%Y"),
+            "month" => date_part("%m"),
+            _ => details.meeting_type.clone(),
+        };
+        let value = if obsidian {
+            note_name(&value)
+        } else {
+            sanitize(&value)
+        };
+        name = name.replace(&format!("{{{}}}", placeholder), &value);
+    }
+    name
+}
+
+/// Lowercase words joined by hyphens, e.g. "Q3 Planning: Kickoff" becomes
+/// "q3-planning-kickoff".
+pub fn slugify(title: &str) -> String {
+    let slug = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "untitled".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Replace characters that are not allowed in file names on common systems,
+/// falling back to "untitled" when nothing is left.
+pub fn sanitize(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    // Leading dots would hide the file
+    let cleaned = cleaned.trim().trim_start_matches('.').trim_start();
+    if cleaned.is_empty() {
+        "untitled".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// `2025-01-15-standup.md` becomes `2025-01-15-standup-<id>.md`.
+fn with_suffix(path: &str, id: &str) -> String {
+    match path.rsplit_once('.') {
+        Some((stem, ext)) if !ext.contains('/') => format!("{}-{}.{}", stem, sanitize(id), ext),
+        _ => format!("{}-{}", path, sanitize(id)),
+    }
+}
@@ -0,0 +1,275 @@
+use crate::commands::documents::NotesFormat;
+use crate::commands::export::ExportFilters;
+use crate::commands::transcript::TranscriptOptions;
+use crate::commands::{get_documents, get_meeting_details, get_transcript};
+use crate::error::{GranolaError, Result};
+use crate::models::{Cache, CacheDocument, ExportOutput, ExportedFile, Person};
+use crate::speakers::SpeakerMapping;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use std::path::PathBuf;
+
+/// Bumped when the tables change, so an old database is not half-updated
+const SCHEMA_VERSION: i32 = 2;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS meetings (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    type TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    duration_minutes INTEGER,
+    overview TEXT
+);
+CREATE TABLE IF NOT EXISTS people (
+    id INTEGER PRIMARY KEY,
+    -- Lowercased email, or name for people without one
+    key TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    email TEXT
+);
+CREATE TABLE IF NOT EXISTS meeting_people (
+    meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    person_id INTEGER NOT NULL REFERENCES people(id),
+    -- 'creator' or 'attendee'
+    role TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, person_id, role)
+);
+CREATE TABLE IF NOT EXISTS segments (
+    meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    speaker TEXT NOT NULL,
+    -- 'microphone' or 'system', as recorded by Granola
+    source TEXT NOT NULL,
+    text TEXT NOT NULL,
+    -- Seconds from the start of the meeting
+    ts INTEGER NOT NULL,
+    PRIMARY KEY (meeting_id, position)
+);
+CREATE TABLE IF NOT EXISTS notes (
+    meeting_id TEXT PRIMARY KEY REFERENCES meetings(id) ON DELETE CASCADE,
+    plain TEXT,
+    markdown TEXT
+);
+-- One row per meeting and kind: 'title', 'overview', 'notes' or 'transcript'
+CREATE TABLE IF NOT EXISTS search_text (
+    id INTEGER PRIMARY KEY,
+    meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    text TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS search_text_meeting ON search_text (meeting_id);
+-- Indexes search_text by rowid, so a meeting's rows are replaced without
+-- scanning the whole index; the triggers keep the two in step
+CREATE VIRTUAL TABLE IF NOT EXISTS meetings_fts USING fts5(
+    meeting_id UNINDEXED,
+    kind UNINDEXED,
+    text,
+    content = 'search_text',
+    content_rowid = 'id'
+);
+CREATE TRIGGER IF NOT EXISTS search_text_insert AFTER INSERT ON search_text BEGIN
+    INSERT INTO meetings_fts (rowid, meeting_id, kind, text)
+    VALUES (new.id, new.meeting_id, new.kind, new.text);
+END;
+CREATE TRIGGER IF NOT EXISTS search_text_delete AFTER DELETE ON search_text BEGIN
+    INSERT INTO meetings_fts (meetings_fts, rowid, meeting_id, kind, text)
+    VALUES ('delete', old.id, old.meeting_id, old.kind, old.text);
+END;
+";
+
+#[derive(Debug)]
+pub struct SqliteExportOptions {
+    pub path: PathBuf,
+    pub filters: ExportFilters,
+    /// Leave meetings alone when their `updated_at` and transcript length
+    /// haven't changed
+    pub incremental: bool,
+}
+
+/// Upsert meetings into a SQLite database, creating the tables if needed.
+pub fn export_sqlite(options: &SqliteExportOptions, cache: &Cache) -> Result<ExportOutput> {
+    let mut conn = Connection::open(&options.path)?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+    check_schema_version(&conn, &options.path)?;
+    conn.execute_batch(SCHEMA)?;
+
+    // One transaction, so a failed export leaves the previous data intact
+    let tx = conn.transaction()?;
+    let mut files = Vec::new();
+    for doc in options.filters.select(cache) {
+        let segments = cache.transcripts.get(&doc.id).map_or(0, Vec::len);
+        let unchanged = options.incremental && is_current(&tx, doc, segments)?;
+        if !unchanged {
+            write_meeting(&tx, doc, cache)?;
+        }
+        files.push(ExportedFile {
+            meeting_id: doc.id.clone(),
+            path: None,
+            status: if unchanged { "unchanged" } else { "written" }.to_string(),
+        });
+    }
+    tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    tx.commit()?;
+
+    let written = files.iter().filter(|f| f.status == "written").count();
+    Ok(ExportOutput {
+        destination: options.path.display().to_string(),
+        total_meetings: files.len(),
+        written,
+        unchanged: files.len() - written,
+        files,
+        removed_files: Vec::new(),
+        people_files: Vec::new(),
+    })
+}
+
+fn check_schema_version(conn: &Connection, path: &std::path::Path) -> Result<()> {
+    let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version != 0 && version != SCHEMA_VERSION {
+        return Err(GranolaError::InvalidArguments(format!(
+            "{} was created by a different version of granola (schema {}, expected {}); export to a new file",
+            path.display(),
+            version,
+            SCHEMA_VERSION
+        )));
+    }
+    Ok(())
+}
+
+/// Whether the stored meeting has the same `updated_at` and transcript length.
+fn is_current(tx: &Transaction, doc: &CacheDocument, segments: usize) -> Result<bool> {
+    let stored: Option<(String, usize)> = tx
+        .query_row(
+            "SELECT updated_at, (SELECT count(*) FROM segments WHERE meeting_id = ?1)
+             FROM meetings WHERE id = ?1",
+            params![doc.id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    Ok(stored.is_some_and(|(updated_at, count)| updated_at == doc.updated_at && count == segments))
+}
+
+/// Replace everything stored for one meeting.
+fn write_meeting(tx: &Transaction, doc: &CacheDocument, cache: &Cache) -> Result<()> {
+    let details = get_meeting_details(&doc.id, cache)?;
+    tx.execute(
+        "INSERT INTO meetings (id, title, type, created_at, updated_at, duration_minutes, overview)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT (id) DO UPDATE SET
+            title = excluded.title,
+            type = excluded.type,
+            created_at = excluded.created_at,
+            updated_at = excluded.updated_at,
+            duration_minutes = excluded.duration_minutes,
+            overview = excluded.overview",
+        params![
+            doc.id,
+            doc.title,
+            doc.doc_type,
+            doc.created_at,
+            doc.updated_at,
+            details.duration_minutes,
+            doc.overview,
+        ],
+    )?;
+
+    for table in ["meeting_people", "segments", "notes", "search_text"] {
+        tx.execute(
+            &format!("DELETE FROM {} WHERE meeting_id = ?1", table),
+            params![doc.id],
+        )?;
+    }
+
+    let people = doc.people.as_ref();
+    let creator = people.and_then(|p| p.creator.as_ref());
+    let attendees = people.and_then(|p| p.attendees.as_deref()).unwrap_or(&[]);
+    for (person, role) in creator
+        .map(|c| (c, "creator"))
+        .into_iter()
+        .chain(attendees.iter().map(|a| (a, "attendee")))
+    {
+        let person_id = upsert_person(tx, person)?;
+        tx.execute(
+            "INSERT OR IGNORE INTO meeting_people (meeting_id, person_id, role) VALUES (?1, ?2, ?3)",
+            params![doc.id, person_id, role],
+        )?;
+    }
+
+    let options = TranscriptOptions {
+        speaker_mapping: SpeakerMapping::from_participants(),
+        ..Default::default()
+    };
+    let mut transcript_text = String::new();
+    if let Ok(transcript) = get_transcript(&doc.id, &options, cache) {
+        let mut insert = tx.prepare_cached(
+            "INSERT INTO segments (meeting_id, position, speaker, source, text, ts)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        // Without a window or merging, segments match the cached ones one to one
+        let raw = cache
+            .transcripts
+            .get(&doc.id)
+            .map_or(&[][..], Vec::as_slice);
+        for (position, (segment, raw)) in transcript.segments.iter().zip(raw).enumerate() {
+            insert.execute(params![
+                doc.id, position, segment.s, raw.source, segment.t, segment.ts
+            ])?;
+            transcript_text.push_str(&format!("{}: {}\n", segment.s, segment.t));
+        }
+    }
+
+    let documents = get_documents(&doc.id, NotesFormat::Both, cache)?;
+    let note = |format: &str| {
+        documents
+            .documents
+            .iter()
+            .find(|d| d.doc_type == "meeting_notes" && d.format == format)
+            .map(|d| d.content.clone())
+    };
+    let (plain, markdown) = (note("plain"), note("markdown"));
+    if plain.is_some() || markdown.is_some() {
+        tx.execute(
+            "INSERT INTO notes (meeting_id, plain, markdown) VALUES (?1, ?2, ?3)",
+            params![doc.id, plain, markdown],
+        )?;
+    }
+
+    let searchable = [
+        ("title", Some(doc.title.clone())),
+        ("overview", doc.overview.clone()),
+        ("notes", plain.or(markdown)),
+        (
+            "transcript",
+            Some(transcript_text).filter(|t| !t.is_empty()),
+        ),
+    ];
+    for (kind, text) in searchable {
+        if let Some(text) = text {
+            tx.execute(
+                "INSERT INTO search_text (meeting_id, kind, text) VALUES (?1, ?2, ?3)",
+                params![doc.id, kind, text],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// The person's row ID, adding them or refreshing their name and email.
+fn upsert_person(tx: &Transaction, person: &Person) -> Result<i64> {
+    let key = person
+        .email
+        .as_deref()
+        .filter(|e| !e.is_empty())
+        .unwrap_or(&person.name)
+        .to_lowercase();
+    Ok(tx.query_row(
+        "INSERT INTO people (key, name, email) VALUES (?1, ?2, ?3)
+         ON CONFLICT (key) DO UPDATE SET
+            name = excluded.name,
+            email = coalesce(excluded.email, people.email)
+         RETURNING id",
+        params![key, person.name, person.email],
+        |row| row.get(0),
+    )?)
+}
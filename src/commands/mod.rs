@@ -1,11 +1,21 @@
+pub mod actions;
 pub mod details;
 pub mod documents;
+pub mod export;
+pub mod export_sqlite;
+pub mod schema;
 pub mod search;
+pub mod stats;
 pub mod transcript;
 pub mod workflow;
 
+pub use actions::find_action_items;
 pub use details::get_meeting_details;
-pub use documents::get_documents;
+pub use documents::{get_documents, get_notes_outline, get_notes_section};
+pub use export::export_markdown;
+pub use export_sqlite::export_sqlite;
+pub use schema::get_schema;
 pub use search::search_meetings;
+pub use stats::get_transcript_stats;
 pub use transcript::get_transcript;
 pub use workflow::get_workflow_guide;
@@ -0,0 +1,179 @@
+use crate::models::{
+    ActionsOutput, ConfigOutput, DocumentsOutput, ErrorOutput, ExportOutput, MeetingDetails,
+    NotesOutline, SearchOutput, TranscriptOutput, TranscriptStats, WatchEvent,
+};
+use clap::ValueEnum;
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde_json::{Map, Value};
+
+/// Output types that have a published schema, named after the command that
+/// produces them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaTarget {
+    Search,
+    Details,
+    Transcript,
+    Stats,
+    Documents,
+    /// `documents --outline`
+    Outline,
+    Actions,
+    /// `config show`
+    Config,
+    Export,
+    /// One line of `watch` output
+    Watch,
+    /// The object written by `--json-errors`
+    Error,
+}
+
+impl SchemaTarget {
+    pub const ALL: [SchemaTarget; 11] = [
+        SchemaTarget::Search,
+        SchemaTarget::Details,
+        SchemaTarget::Transcript,
+        SchemaTarget::Stats,
+        SchemaTarget::Documents,
+        SchemaTarget::Outline,
+        SchemaTarget::Actions,
+        SchemaTarget::Config,
+        SchemaTarget::Export,
+        SchemaTarget::Watch,
+        SchemaTarget::Error,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SchemaTarget::Search => "search",
+            SchemaTarget::Details => "details",
+            SchemaTarget::Transcript => "transcript",
+            SchemaTarget::Stats => "stats",
+            SchemaTarget::Documents => "documents",
+            SchemaTarget::Outline => "outline",
+            SchemaTarget::Actions => "actions",
+            SchemaTarget::Config => "config",
+            SchemaTarget::Export => "export",
+            SchemaTarget::Watch => "watch",
+            SchemaTarget::Error => "error",
+        }
+    }
+
+    /// JSON Schema generated from the output struct.
+    pub fn schema(self) -> Value {
+        match self {
+            SchemaTarget::Search => schema_for::<SearchOutput>(),
+            SchemaTarget::Details => schema_for::<MeetingDetails>(),
+            SchemaTarget::Transcript => schema_for::<TranscriptOutput>(),
+            SchemaTarget::Stats => schema_for::<TranscriptStats>(),
+            SchemaTarget::Documents => schema_for::<DocumentsOutput>(),
+            SchemaTarget::Outline => schema_for::<NotesOutline>(),
+            SchemaTarget::Actions => schema_for::<ActionsOutput>(),
+            SchemaTarget::Config => schema_for::<ConfigOutput>(),
+            SchemaTarget::Export => schema_for::<ExportOutput>(),
+            SchemaTarget::Watch => schema_for::<WatchEvent>(),
+            SchemaTarget::Error => schema_for::<ErrorOutput>(),
+        }
+    }
+}
+
+/// The schema for one command, or an object of all schemas keyed by command.
+pub fn get_schema(target: Option<SchemaTarget>) -> Value {
+    match target {
+        Some(target) => target.schema(),
+        None => Value::Object(
+            SchemaTarget::ALL
+                .iter()
+                .map(|t| (t.name().to_string(), t.schema()))
+                .collect(),
+        ),
+    }
+}
+
+pub(crate) fn schema_for<T: JsonSchema>() -> Value {
+    // Inline nested types so each schema is self-contained
+    SchemaSettings::draft2020_12()
+        .with(|s| s.inline_subschemas = true)
+        .into_generator()
+        .into_root_schema_for::<T>()
+        .to_value()
+}
+
+/// Render a schema as the compact JSON-like outline used in the workflow
+/// guide, e.g. `{"id": string, "summary": string | null}`.
+pub fn schema_outline(schema: &Value) -> String {
+    outline(schema, 0)
+}
+
+fn outline(schema: &Value, indent: usize) -> String {
+    let Some(schema) = schema.as_object() else {
+        return "any".to_string();
+    };
+
+    // Recursive types refer back to their definition by name
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return reference.rsplit('/').next().unwrap_or("any").to_string();
+    }
+
+    // Option<Struct> is generated as `anyOf: [<struct>, {"type": "null"}]`
+    if let Some(variants) = schema.get("anyOf").and_then(Value::as_array) {
+        let parts: Vec<String> = variants.iter().map(|v| outline(v, indent)).collect();
+        return parts.join(" | ");
+    }
+
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        return object_outline(properties, indent);
+    }
+
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    };
+
+    let parts: Vec<String> = types
+        .iter()
+        .map(|t| match *t {
+            "array" => format!(
+                "[{}]",
+                schema
+                    .get("items")
+                    .map(|items| outline(items, indent))
+                    .unwrap_or_else(|| "any".to_string())
+            ),
+            "integer" => "number".to_string(),
+            other => other.to_string(),
+        })
+        .collect();
+
+    if parts.is_empty() {
+        "any".to_string()
+    } else {
+        parts.join(" | ")
+    }
+}
+
+fn object_outline(properties: &Map<String, Value>, indent: usize) -> String {
+    let pad = " ".repeat(indent + 2);
+    let mut lines = Vec::new();
+
+    for (i, (name, schema)) in properties.iter().enumerate() {
+        let value = outline(schema, indent + 2);
+        let comma = if i + 1 < properties.len() { "," } else { "" };
+        let mut line = format!("{}\"{}\": {}{}", pad, name, value, comma);
+
+        // Field docs become trailing comments on single-line fields
+        let description = schema
+            .get("description")
+            .and_then(Value::as_str)
+            .and_then(|d| d.lines().next());
+        if let Some(description) = description {
+            if !value.contains('\n') {
+                line.push_str(&format!("  // {}", description));
+            }
+        }
+        lines.push(line);
+    }
+
+    format!("{{\n{}\n{}}}", lines.join("\n"), " ".repeat(indent))
+}
@@ -1,6 +1,9 @@
 use crate::error::Result;
 use crate::models::{Cache, SearchOutput, SearchResult};
 
+/// Results returned when no limit is configured
+pub const DEFAULT_LIMIT: usize = 30;
+
 pub fn search_meetings(query: &str, limit: usize, cache: &Cache) -> Result<SearchOutput> {
     let query_lower = query.to_lowercase();
 
@@ -17,7 +20,7 @@ pub fn search_meetings(query: &str, limit: usize, cache: &Cache) -> Result<Searc
                 participants,
                 summary: doc.overview.clone(),
                 has_transcript: cache.transcripts.contains_key(&doc.id),
-                has_notes: doc.notes_plain.is_some() || doc.notes_markdown.is_some(),
+                has_notes: doc.has_notes(),
             }
         })
         .collect();
@@ -37,7 +40,8 @@ pub fn search_meetings(query: &str, limit: usize, cache: &Cache) -> Result<Searc
     })
 }
 
-fn matches_query(doc: &crate::models::CacheDocument, query_lower: &str) -> bool {
+/// Whether a meeting matches a search query, which must be lowercase.
+pub fn matches_query(doc: &crate::models::CacheDocument, query_lower: &str) -> bool {
     // Check title
     if doc.title.to_lowercase().contains(query_lower) {
         return true;
@@ -0,0 +1,130 @@
+use crate::error::{GranolaError, Result};
+use crate::models::{Cache, SilenceGap, SpeakerStats, TranscriptSegment, TranscriptStats};
+use crate::speakers::SpeakerMapping;
+
+/// Average speaking rate used to estimate how long a segment was spoken for
+const WORDS_PER_SECOND: f64 = 2.5;
+
+/// Pauses at least this long are reported as silence gaps
+const SILENCE_GAP_SECONDS: i64 = 10;
+
+/// A new speaker starting before the previous one would have finished, or
+/// less than this after, counts as an interruption
+const INTERRUPTION_GAP_SECONDS: i64 = 1;
+
+pub fn get_transcript_stats(
+    meeting_id: &str,
+    speaker_mapping: &SpeakerMapping,
+    cache: &Cache,
+) -> Result<TranscriptStats> {
+    let doc = cache
+        .documents
+        .get(meeting_id)
+        .ok_or_else(|| GranolaError::MeetingNotFound(meeting_id.to_string()))?;
+
+    let segments = cache
+        .transcripts
+        .get(meeting_id)
+        .ok_or_else(|| GranolaError::TranscriptNotFound(meeting_id.to_string()))?;
+
+    let labels = speaker_mapping.labels_for(doc);
+    let mut speakers: Vec<SpeakerStats> = Vec::new();
+    let mut silence_gaps = Vec::new();
+
+    // Time spoken in the current monologue, which a silence gap ends
+    let mut monologue = 0;
+    // Where the previous segment ended, capped by the next one's start
+    let mut previous_end = 0;
+    // When the previous speaker would have finished, uncapped
+    let mut previous_spoken_end = 0;
+
+    for (i, segment) in segments.iter().enumerate() {
+        let next = segments.get(i + 1);
+        let words = segment.text.split_whitespace().count();
+        let spoken = estimated_speech_seconds(segment);
+
+        // A segment cannot run past the start of the next one
+        let end = match next {
+            Some(n) => (segment.timestamp + spoken).min(n.timestamp.max(segment.timestamp)),
+            None => segment.timestamp + spoken,
+        };
+
+        let previous = i.checked_sub(1).map(|p| &segments[p]);
+        let new_turn = previous.is_none_or(|p| p.source != segment.source);
+        let interrupted = previous.is_some_and(|p| {
+            p.source != segment.source
+                && segment.timestamp < previous_spoken_end + INTERRUPTION_GAP_SECONDS
+        });
+        let after_silence =
+            previous.is_some() && segment.timestamp - previous_end >= SILENCE_GAP_SECONDS;
+
+        let speaker = labels.label(&segment.source);
+        let stats = match speakers.iter().position(|s| s.speaker == speaker) {
+            Some(pos) => &mut speakers[pos],
+            None => {
+                speakers.push(SpeakerStats {
+                    speaker,
+                    talk_time_seconds: 0,
+                    talk_time_percent: 0.0,
+                    word_count: 0,
+                    segment_count: 0,
+                    turn_count: 0,
+                    longest_monologue_seconds: 0,
+                    interruptions: 0,
+                });
+                speakers.last_mut().unwrap()
+            }
+        };
+
+        stats.talk_time_seconds += end - segment.timestamp;
+        stats.word_count += words;
+        stats.segment_count += 1;
+        if new_turn {
+            stats.turn_count += 1;
+        }
+        if new_turn || after_silence {
+            monologue = 0;
+        }
+        monologue += end - segment.timestamp;
+        previous_end = end;
+        previous_spoken_end = segment.timestamp + spoken;
+        if interrupted {
+            stats.interruptions += 1;
+        }
+        stats.longest_monologue_seconds = stats.longest_monologue_seconds.max(monologue);
+
+        if let Some(n) = next {
+            if n.timestamp - end >= SILENCE_GAP_SECONDS {
+                silence_gaps.push(SilenceGap {
+                    start: end,
+                    end: n.timestamp,
+                    duration_seconds: n.timestamp - end,
+                });
+            }
+        }
+    }
+
+    let total_talk_time_seconds: i64 = speakers.iter().map(|s| s.talk_time_seconds).sum();
+    for stats in &mut speakers {
+        if total_talk_time_seconds > 0 {
+            let percent = stats.talk_time_seconds as f64 * 100.0 / total_talk_time_seconds as f64;
+            stats.talk_time_percent = (percent * 10.0).round() / 10.0;
+        }
+    }
+
+    Ok(TranscriptStats {
+        id: doc.id.clone(),
+        title: doc.title.clone(),
+        duration_seconds: segments.last().map(|s| s.timestamp),
+        total_segments: segments.len(),
+        total_talk_time_seconds,
+        total_silence_seconds: silence_gaps.iter().map(|g| g.duration_seconds).sum(),
+        speakers,
+        silence_gaps,
+    })
+}
+
+fn estimated_speech_seconds(segment: &TranscriptSegment) -> i64 {
+    let words = segment.text.split_whitespace().count() as f64;
+    ((words / WORDS_PER_SECOND).ceil() as i64).max(1)
+}
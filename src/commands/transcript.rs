@@ -1,8 +1,81 @@
 use crate::error::{GranolaError, Result};
-use crate::models::{Cache, CompactSegment, TranscriptOutput};
-use std::collections::HashSet;
+use crate::models::{Cache, CompactSegment, TranscriptOutput, TranscriptWindow};
+use crate::speakers::SpeakerMapping;
 
-pub fn get_transcript(meeting_id: &str, cache: &Cache) -> Result<TranscriptOutput> {
+/// A point in a transcript, either relative to the start or to the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOffset {
+    FromStart(i64),
+    FromEnd(i64),
+}
+
+impl TimeOffset {
+    /// Parse seconds (`90`), `mm:ss` or `hh:mm:ss`, optionally prefixed with
+    /// `-` to count back from the end of the transcript.
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        let (negative, rest) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+
+        let parts: Vec<&str> = rest.split(':').collect();
+        if parts.is_empty() || parts.len() > 3 {
+            return Err(invalid_time(input));
+        }
+
+        let mut seconds: i64 = 0;
+        for (i, part) in parts.iter().enumerate() {
+            let value: i64 = part.parse().map_err(|_| invalid_time(input))?;
+            // Minutes and seconds must stay below 60 once a larger unit is given
+            if value < 0 || (i > 0 && value >= 60) {
+                return Err(invalid_time(input));
+            }
+            seconds = seconds
+                .checked_mul(60)
+                .and_then(|s| s.checked_add(value))
+                .ok_or_else(|| invalid_time(input))?;
+        }
+
+        Ok(if negative {
+            TimeOffset::FromEnd(seconds)
+        } else {
+            TimeOffset::FromStart(seconds)
+        })
+    }
+
+    /// Seconds from the start of a transcript ending at `end`.
+    pub fn resolve(self, end: i64) -> i64 {
+        match self {
+            TimeOffset::FromStart(s) => s,
+            TimeOffset::FromEnd(s) => (end - s).max(0),
+        }
+    }
+}
+
+fn invalid_time(input: &str) -> GranolaError {
+    GranolaError::InvalidArguments(format!(
+        "Invalid time '{}': expected seconds, mm:ss or hh:mm:ss (prefix with '-' to count from the end)",
+        input
+    ))
+}
+
+#[derive(Debug, Default)]
+pub struct TranscriptOptions {
+    pub from: Option<TimeOffset>,
+    pub to: Option<TimeOffset>,
+    /// Coalesce consecutive segments from the same speaker into one turn
+    pub merge_turns: bool,
+    /// Only keep segments from this speaker (case-insensitive)
+    pub speaker: Option<String>,
+    pub speaker_mapping: SpeakerMapping,
+}
+
+pub fn get_transcript(
+    meeting_id: &str,
+    options: &TranscriptOptions,
+    cache: &Cache,
+) -> Result<TranscriptOutput> {
     let doc = cache
         .documents
         .get(meeting_id)
@@ -13,28 +86,96 @@ pub fn get_transcript(meeting_id: &str, cache: &Cache) -> Result<TranscriptOutpu
         .get(meeting_id)
         .ok_or_else(|| GranolaError::TranscriptNotFound(meeting_id.to_string()))?;
 
-    // Extract unique speakers
-    let speakers: HashSet<String> = segments.iter().map(|s| s.source.clone()).collect();
-    let speakers: Vec<String> = speakers.into_iter().collect();
+    let duration_seconds = segments.last().map(|s| s.timestamp);
+    let labels = options.speaker_mapping.labels_for(doc);
+
+    // Resolve the requested time window against the transcript length
+    let window = if options.from.is_some() || options.to.is_some() {
+        let end = duration_seconds.unwrap_or(0);
+        let from = options.from.map(|o| o.resolve(end)).unwrap_or(0);
+        let to = options.to.map(|o| o.resolve(end)).unwrap_or(end);
+        if from > to {
+            return Err(GranolaError::InvalidArguments(format!(
+                "Time window is empty: from {}s is after to {}s",
+                from, to
+            )));
+        }
+        Some(TranscriptWindow {
+            from_seconds: from,
+            to_seconds: to,
+        })
+    } else {
+        None
+    };
+
+    let segments: Vec<_> = segments
+        .iter()
+        .filter(|s| match &window {
+            Some(w) => s.timestamp >= w.from_seconds && s.timestamp <= w.to_seconds,
+            None => true,
+        })
+        .collect();
 
     // Convert to compact format
-    let compact_segments: Vec<CompactSegment> = segments
+    let mut compact_segments: Vec<CompactSegment> = segments
         .iter()
         .map(|s| CompactSegment {
-            s: s.source.clone(),
+            s: labels.label(&s.source),
             t: s.text.clone(),
             ts: s.timestamp,
+            te: None,
         })
         .collect();
 
-    let duration_seconds = segments.last().map(|s| s.timestamp);
+    // Merge before filtering so turns are not glued together across the
+    // segments of a speaker that was filtered out
+    if options.merge_turns {
+        compact_segments = merge_turns(compact_segments);
+    }
+
+    if let Some(speaker) = &options.speaker {
+        compact_segments.retain(|s| labels.matches(&s.s, speaker));
+    }
+
+    // Extract unique speakers in order of first appearance
+    let mut speakers: Vec<String> = Vec::new();
+    for segment in &compact_segments {
+        if !speakers.contains(&segment.s) {
+            speakers.push(segment.s.clone());
+        }
+    }
 
     Ok(TranscriptOutput {
         id: doc.id.clone(),
         title: doc.title.clone(),
         duration_seconds,
+        window,
         speakers,
         total_segments: compact_segments.len(),
         segments: compact_segments,
     })
 }
+
+fn merge_turns(segments: Vec<CompactSegment>) -> Vec<CompactSegment> {
+    let mut turns: Vec<CompactSegment> = Vec::new();
+
+    for segment in segments {
+        match turns.last_mut() {
+            Some(turn) if turn.s == segment.s => {
+                if !segment.t.is_empty() {
+                    if !turn.t.is_empty() {
+                        turn.t.push(' ');
+                    }
+                    turn.t.push_str(&segment.t);
+                }
+                turn.te = Some(segment.ts);
+            }
+            _ => turns.push(CompactSegment {
+                te: Some(segment.ts),
+                ..segment
+            }),
+        }
+    }
+
+    turns
+}
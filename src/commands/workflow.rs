@@ -1,5 +1,65 @@
+use crate::commands::schema::{schema_outline, SchemaTarget};
+use crate::render::version::SchemaVersion;
+
 pub fn get_workflow_guide() -> String {
-    WORKFLOW_GUIDE.to_string()
+    let schemas: Vec<String> = SchemaTarget::ALL
+        .iter()
+        .filter(|t| **t != SchemaTarget::Error)
+        .map(|t| {
+            let mut section = format!(
+                "### {} Output\n```json\n{}\n```",
+                title_case(t.name()),
+                schema_outline(&t.schema())
+            );
+            if let Some(note) = schema_note(*t) {
+                section.push_str(&format!("\n*Note: {}*", note));
+            }
+            section
+        })
+        .collect();
+
+    WORKFLOW_GUIDE
+        .replace("{schemas}", &schemas.join("\n\n"))
+        .replace(
+            "{error_schema}",
+            &schema_outline(&SchemaTarget::Error.schema()),
+        )
+        .replace("{schema_version}", SchemaVersion::CURRENT.as_str())
+}
+
+fn schema_note(target: SchemaTarget) -> Option<&'static str> {
+    match target {
+        SchemaTarget::Transcript => Some(
+            "segments use short keys (s/t/ts) for token efficiency. `window` is only present when `--from`/`--to` is given, and `te` only with `--merge-turns`.",
+        ),
+        SchemaTarget::Outline => Some(
+            "returned by `documents <id> --outline`. `children` holds nested headings of the same shape.",
+        ),
+        SchemaTarget::Actions => Some(
+            "items are grouped by meeting, newest first. `ts` is only present on transcript items.",
+        ),
+        SchemaTarget::Config => Some(
+            "returned by `config show`. `source` says where each value came from, so you can tell which setting won.",
+        ),
+        SchemaTarget::Export => Some(
+            "a summary of what `export` wrote; the meetings themselves go to the files or database.",
+        ),
+        SchemaTarget::Watch => Some(
+            "one object per line of `watch` output. `previous_transcript_segments` is only present on `transcript_extended`.",
+        ),
+        SchemaTarget::Stats => Some(
+            "talk time is estimated from word count and capped by the next segment's start. An interruption is counted when the speaker changes before the previous speaker's estimated end, or less than 1s after it. Silence gaps are pauses of 10s or more, and end a monologue.",
+        ),
+        _ => None,
+    }
+}
+
+fn title_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
 }
 
 const WORKFLOW_GUIDE: &str = r#"# Granola CLI Workflow Guide
@@ -11,7 +71,14 @@ Query local Granola meeting data via CLI. Optimized for LLM consumption with JSO
 - `search <query>` - Find meetings (returns summaries for context)
 - `details <id>` - Get meeting metadata
 - `transcript <id>` - Get full conversation with speakers
+- `stats <id>` - Get per-speaker talk time and conversation statistics
 - `documents <id>` - Get notes and overviews
+- `actions` - Get action items across all meetings
+- `watch` - Stream meeting changes as NDJSON events
+- `export --dir <path>` - Write meetings to Markdown files with YAML front matter
+  (`--obsidian` for a vault with person notes and wikilinks)
+- `export --sqlite <file>` - Write meetings, people, transcript segments and notes to a
+  SQLite database with full-text search
 
 ## Common Patterns
 
@@ -24,6 +91,70 @@ Query local Granola meeting data via CLI. Optimized for LLM consumption with JSO
 ### Finding Recent Meetings with Someone
 `granola search "person_name" --limit 20`
 
+### Reading Part of a Transcript
+```bash
+granola transcript <id> --from 12:30 --to 20:00   # a slice of the call
+granola transcript <id> --from -10:00             # the last 10 minutes
+```
+Times accept seconds, `mm:ss` or `hh:mm:ss`; a leading `-` counts back from the end.
+
+### Condensing a Transcript
+```bash
+granola transcript <id> --merge-turns             # one entry per speaker turn
+granola transcript <id> --speaker system          # only one side of the call
+```
+
+### Naming Speakers
+Raw speakers are `microphone` (the person recording) and `system` (everyone else on the call).
+```bash
+granola transcript <id> --map-speakers                  # guess names from creator/attendees
+granola transcript <id> --speaker-map names.json        # {"microphone": "Alice", "system": "Bob"}
+```
+`--map-speakers` names `system` only when there is exactly one other attendee. Both flags also work with `stats`.
+
+### Exporting Subtitles
+```bash
+granola transcript <id> --format srt > meeting.srt
+granola transcript <id> --format vtt --map-speakers > meeting.vtt
+```
+Cues end when the next segment starts, capped at 7 seconds. VTT cues carry the speaker as a `<v>` voice tag.
+
+### Reading One Section of the Notes
+```bash
+granola documents <id> --outline                  # heading tree of the notes
+granola documents <id> --section "Next steps"     # just that section, as markdown
+```
+Section names match case-insensitively and include their subsections.
+
+### Collecting Action Items
+```bash
+granola actions --since 2025-01-01 --owner alice --open
+granola actions --transcripts                     # also scan speech for "I'll ..." commitments
+```
+Items come from checklists, `TODO:`/`Action:` lines and lists under headings like "Next steps".
+`assignee` is a guess from `@mentions`, a leading "Name to ...", "Participant: ..." or a participant named in the item.
+
+### Querying Repeatedly
+`granola serve` keeps the cache loaded and answers `GET /search?q=...`, `/meetings/<id>`,
+`/meetings/<id>/transcript` and `/meetings/<id>/documents` with the same JSON as the commands.
+Query parameters mirror the flags (`limit`, `from`, `to`, `merge_turns`, `section`, ...).
+
+### Using MCP Instead of the CLI
+`granola mcp` serves the same data over stdio as MCP tools (`search_meetings`,
+`get_meeting_details`, `get_transcript`, `get_documents`) and `granola://meetings/<id>` resources.
+
+### Reacting to New Meetings
+`granola watch` runs until stopped and prints one JSON object per line whenever the
+cache changes: `meeting_created`, `meeting_updated`, `transcript_extended` or
+`notes_changed`, with `meeting_id` to fetch the rest.
+```bash
+granola watch | while read -r event; do echo "$event" | jq -r .meeting_id; done
+```
+
+`granola hooks run` does the same for `[[hooks.on_new_meeting]]`,
+`[[hooks.on_transcript_complete]]` and `[[hooks.on_notes_updated]]` entries in the
+config file, running a command or POSTing the meeting JSON at least once per event.
+
 ### Getting Full Context for a Meeting
 ```bash
 # Get all three in sequence
@@ -36,77 +167,44 @@ granola documents <id>
 
 1. **Use --limit wisely**: Default is 30. Lower it if you just need recent matches.
 2. **Search returns summaries**: Use these to filter before fetching transcripts
-3. **Transcripts are large**: 25k+ chars typical. Only fetch when needed.
-4. **Documents are smaller**: Notes/overviews are more compact than transcripts
-5. **Check has_transcript/has_notes**: Avoid fetching what doesn't exist
+3. **Transcripts are large**: 25k+ chars typical. Only fetch when needed, and use `--from`/`--to` to fetch just the part you need.
+   `--merge-turns` removes repeated speaker labels and cuts segment count substantially.
+4. **Use stats for "who talked most"**: `stats <id>` answers it without fetching the transcript
+5. **Documents are smaller**: Notes/overviews are more compact than transcripts.
+   Use `--notes-format best` to keep headings/lists only when the notes have them,
+   or `--outline` then `--section` to fetch only the part you need
+6. **Check has_transcript/has_notes**: Avoid fetching what doesn't exist
+7. **Project fields**: `--fields results.id,results.title,results.date --compact` drops
+   participants and summaries you don't need. Paths are dotted and pass through arrays.
 
 ## Output Schemas
+Outlines below are generated from the output types. Run `granola schema <command>` for the
+full JSON Schema.
 
-### Search Output
-```json
-{
-  "query": "string",
-  "total_matches": 0,
-  "results": [{
-    "id": "uuid",
-    "title": "string",
-    "date": "ISO-8601",
-    "participants": ["string"],
-    "summary": "string | null",
-    "has_transcript": boolean,
-    "has_notes": boolean
-  }]
-}
-```
+{schemas}
 
-### Details Output
-```json
-{
-  "id": "uuid",
-  "title": "string",
-  "date": "ISO-8601",
-  "duration_minutes": number | null,
-  "participants": [{"name": "string", "email": "string | null"}],
-  "type": "string",
-  "has_transcript": boolean,
-  "has_notes": boolean,
-  "created_at": "ISO-8601",
-  "updated_at": "ISO-8601"
-}
-```
+### Envelope and Versions
+`--envelope` wraps JSON output as
+`{"schema_version", "command", "generated_at", "cache": {"path", "mtime"}, "data"}`.
+Minor schema versions only add fields, so ignore unknown fields. Pin an older layout with
+`--schema-version 1.0` (current: {schema_version}).
 
-### Transcript Output
-```json
-{
-  "id": "uuid",
-  "title": "string",
-  "duration_seconds": number | null,
-  "speakers": ["string"],
-  "total_segments": number,
-  "segments": [
-    {"s": "speaker", "t": "text", "ts": timestamp_seconds}
-  ]
-}
-```
-*Note: segments use short keys (s/t/ts) for token efficiency*
+## Output Formats
+JSON is the default and the best choice for parsing. The other formats cover:
+- `text`, `markdown` - search, details, transcript, stats, documents (and `--outline`),
+  actions, `config show` and export, for humans
+- `ndjson` - search, details, transcript, stats, documents (and `--outline`) and actions
+- `csv` - search
+- `srt`, `vtt` - transcript
 
-### Documents Output
-```json
-{
-  "id": "uuid",
-  "title": "string",
-  "total_documents": number,
-  "documents": [{
-    "id": "uuid",
-    "title": "string",
-    "type": "meeting_notes | overview",
-    "format": "plain | markdown",
-    "content": "string",
-    "word_count": number,
-    "created_at": "ISO-8601"
-  }]
-}
-```
+Other combinations fail with exit code 5. `watch` always writes NDJSON.
+
+`--format ndjson` writes one JSON object per line: one search result, transcript segment or
+document per line (details and stats are a single line). Useful with `jq -c` and for streaming
+very large transcripts.
+
+`--format csv` flattens search results for spreadsheets; choose fields with
+`--columns id,title,date,participants,summary,has_transcript,has_notes`.
 
 ## Error Handling
 
@@ -117,14 +215,7 @@ granola documents <id>
 ### JSON Error Mode
 Use `--json-errors` to get structured errors on stdout:
 ```json
-{
-  "error": {
-    "code": number,
-    "type": "error_type",
-    "message": "string",
-    "suggestion": "string"
-  }
-}
+{error_schema}
 ```
 
 ### Exit Codes
@@ -132,7 +223,7 @@ Use `--json-errors` to get structured errors on stdout:
 - 1: General error
 - 2: Cache file not found
 - 3: Invalid cache format
-- 4: Meeting not found
+- 4: Meeting, transcript or notes section not found
 - 5: Invalid arguments
 
 ## Configuration
@@ -140,7 +231,18 @@ Use `--json-errors` to get structured errors on stdout:
 ### Cache Path Priority
 1. `--cache-path <PATH>` flag
 2. `GRANOLA_CACHE_PATH` environment variable
-3. Default: `~/Library/Application Support/Granola/cache-v3.json`
+3. `cache_path` in the config file
+4. Default: `~/Library/Application Support/Granola/cache-v3.json`
+
+### Config File
+`~/.config/granola/config.toml` (or `GRANOLA_CONFIG`) can set `cache_path`,
+`format`, `limit` and `json_errors` in a `[default]` section and in
+`[profiles.<name>]` sections selected with `--profile <name>`. Flags and
+environment variables still win. If output looks different than expected,
+check what is in effect:
+```bash
+granola config show
+```
 
 ## Examples
 
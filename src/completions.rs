@@ -0,0 +1,255 @@
+use crate::cache;
+use crate::config::{CliOverrides, Settings};
+use crate::error::Result;
+use crate::models::Cache;
+use clap::ValueEnum;
+use clap_complete::Shell;
+use std::collections::BTreeSet;
+use std::io::Write;
+
+/// Set by the completion scripts to ask the binary for meeting IDs instead of
+/// running a command. Kept out of the CLI so it doesn't show up in help or in
+/// the generated completions.
+pub const COMPLETE_ENV: &str = "_GRANOLA_COMPLETE";
+
+/// Subcommands whose first positional argument is a meeting ID
+const MEETING_COMMANDS: &[&str] = &["details", "transcript", "stats", "documents"];
+
+/// How many meetings to offer, newest first
+const MAX_MEETINGS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Write the completion script for `shell`: clap's static completions for
+/// commands and flags, plus a hook that completes meeting IDs from the cache.
+pub fn write_completions(
+    shell: CompletionShell,
+    cmd: &mut clap::Command,
+    w: &mut dyn Write,
+) -> Result<()> {
+    // Complete the binary as installed (`granola-cli`), not the name shown
+    // in help
+    let bin = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| cmd.get_name().to_string());
+    let value_flags = value_flags(cmd);
+    let mut script = Vec::new();
+    let clap_shell = match shell {
+        CompletionShell::Bash => Shell::Bash,
+        CompletionShell::Zsh => Shell::Zsh,
+        CompletionShell::Fish => Shell::Fish,
+    };
+    clap_complete::generate(clap_shell, cmd, &bin, &mut script);
+    let script = String::from_utf8_lossy(&script);
+
+    match shell {
+        CompletionShell::Bash => {
+            w.write_all(script.as_bytes())?;
+            write!(w, "{}", bash_hook(&bin, &value_flags))?;
+        }
+        CompletionShell::Zsh => {
+            // Point the meeting_id argument at our completer instead of files
+            let script = script.replace(
+                ":meeting_id -- Meeting ID:_default",
+                ":meeting_id -- Meeting ID:_granola_meetings",
+            );
+            // `#compdef` must stay the first line, and the helper must exist
+            // before the script calls the main completion function
+            let (compdef, rest) = script.split_once('\n').unwrap_or((&script, ""));
+            writeln!(w, "{}", compdef)?;
+            w.write_all(zsh_hook(&bin).as_bytes())?;
+            w.write_all(rest.as_bytes())?;
+        }
+        CompletionShell::Fish => {
+            w.write_all(script.as_bytes())?;
+            write!(w, "{}", fish_hook(&bin, &value_flags))?;
+        }
+    }
+    Ok(())
+}
+
+/// Every flag that takes a value, anywhere in the command tree. The hooks
+/// skip over their values so a flag value is never taken for a meeting ID.
+fn value_flags(cmd: &clap::Command) -> Vec<String> {
+    let mut flags = BTreeSet::new();
+    let mut pending = vec![cmd];
+    while let Some(cmd) = pending.pop() {
+        for arg in cmd.get_arguments() {
+            if arg.is_positional() || !arg.get_action().takes_values() {
+                continue;
+            }
+            flags.extend(arg.get_long().map(|long| format!("--{}", long)));
+            flags.extend(arg.get_short().map(|short| format!("-{}", short)));
+        }
+        pending.extend(cmd.get_subcommands());
+    }
+    flags.into_iter().collect()
+}
+
+/// Print recent meetings for the shell hooks and return the exit code.
+/// Failures stay quiet: a missing cache just means no suggestions.
+pub fn complete_meetings() -> i32 {
+    let result = Settings::load(CliOverrides::default())
+        .and_then(|settings| cache::load_cache(&settings.cache_path.value))
+        .and_then(|cache| list_meetings(&cache, &mut std::io::stdout().lock()));
+    match result {
+        Ok(()) => 0,
+        Err(_) => 1,
+    }
+}
+
+/// Recent meetings as `id<TAB>title` lines, newest first.
+fn list_meetings(cache: &Cache, w: &mut dyn Write) -> Result<()> {
+    let mut docs: Vec<_> = cache.documents.values().collect();
+    docs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    for doc in docs.into_iter().take(MAX_MEETINGS) {
+        // Tabs and newlines would split the line
+        let title = doc.title.replace(['\t', '\n'], " ");
+        writeln!(w, "{}\t{}", doc.id, title)?;
+    }
+    Ok(())
+}
+
+fn bash_hook(bin: &str, value_flags: &[String]) -> String {
+    format!(
+        r#"
+_granola_with_meetings() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local i word value subcommand="" positionals=0 cache_path="" profile=""
+    # Find the subcommand and its positionals past flag values, remembering
+    # the cache and profile so IDs come from the same cache the command reads
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        word="${{COMP_WORDS[i]}}"
+        case "$word" in
+            {value_flags})
+                # Bash splits `--flag=value` into three words
+                [[ "${{COMP_WORDS[i + 1]}}" == "=" ]] && ((i++))
+                value="${{COMP_WORDS[++i]}}"
+                case "$word" in
+                    --cache-path) cache_path="${{value/#\~/$HOME}}" ;;
+                    --profile) profile="$value" ;;
+                esac
+                ;;
+            -*) ;;
+            *)
+                if [[ -z "$subcommand" ]]; then
+                    subcommand="$word"
+                else
+                    ((positionals++))
+                fi
+                ;;
+        esac
+    done
+    # Skipping a value that is still being typed leaves i past COMP_CWORD
+    case "$subcommand" in
+        {commands})
+            if [[ $positionals -eq 0 && $i -eq $COMP_CWORD && "$cur" != -* ]]; then
+                COMPREPLY=($(compgen -W "$(env ${{cache_path:+"GRANOLA_CACHE_PATH=$cache_path"}} ${{profile:+"GRANOLA_PROFILE=$profile"}} {complete}=meetings {bin} 2>/dev/null | cut -f1)" -- "$cur"))
+                return 0
+            fi
+            ;;
+    esac
+    _{bin} "$@"
+}}
+complete -F _granola_with_meetings -o bashdefault -o default {bin}
+"#,
+        commands = MEETING_COMMANDS.join("|"),
+        value_flags = value_flags.join("|"),
+        bin = bin,
+        complete = COMPLETE_ENV,
+    )
+}
+
+fn zsh_hook(bin: &str) -> String {
+    format!(
+        r#"_granola_meetings() {{
+    local -a meetings cmdline
+    local i line cache_path profile
+    # _arguments has already dropped the flags before the subcommand from
+    # $words, so look for the cache and profile in the typed line
+    cmdline=(${{(Q)${{(z)LBUFFER}}}})
+    for ((i = 2; i <= $#cmdline; i++)); do
+        case "$cmdline[i]" in
+            --cache-path) cache_path="$cmdline[++i]" ;;
+            --cache-path=*) cache_path="${{cmdline[i]#*=}}" ;;
+            --profile) profile="$cmdline[++i]" ;;
+            --profile=*) profile="${{cmdline[i]#*=}}" ;;
+        esac
+    done
+    cache_path="${{cache_path/#\~/$HOME}}"
+    for line in ${{(f)"$(env ${{cache_path:+"GRANOLA_CACHE_PATH=$cache_path"}} ${{profile:+"GRANOLA_PROFILE=$profile"}} {complete}=meetings {bin} 2>/dev/null)"}}; do
+        meetings+=("${{${{line%%$'\t'*}}//:/\\:}}:${{line#*$'\t'}}")
+    done
+    _describe 'meeting' meetings
+}}
+
+"#,
+        bin = bin,
+        complete = COMPLETE_ENV,
+    )
+}
+
+fn fish_hook(bin: &str, value_flags: &[String]) -> String {
+    format!(
+        r#"
+# Succeeds when the next word is the meeting ID of {commands_list}, printing
+# the cache and profile from the command line as environment assignments
+function __{fn_name}_meeting_env
+    set -l tokens (commandline -opc)
+    set -e tokens[1]
+    set -l subcommand
+    set -l positionals 0
+    set -l flag
+    set -l vars
+    for token in $tokens
+        if test -z "$flag"
+            switch $token
+                case {value_flags}
+                    set flag $token
+                    continue
+                case '--*=*'
+                    set -l parts (string split -m 1 = -- $token)
+                    set flag $parts[1]
+                    set token $parts[2]
+                case '-*'
+                    continue
+                case '*'
+                    if test -z "$subcommand"
+                        set subcommand $token
+                    else
+                        set positionals (math $positionals + 1)
+                    end
+                    continue
+            end
+        end
+        switch $flag
+            case --cache-path
+                set -a vars GRANOLA_CACHE_PATH=(string replace -r -- '^~' $HOME $token)
+            case --profile
+                set -a vars GRANOLA_PROFILE=$token
+        end
+        set flag
+    end
+    # Not while a flag's value is being typed, nor past the first positional
+    test -z "$flag"; and test $positionals -eq 0; and contains -- "$subcommand" {commands}
+    or return 1
+    string join \n -- $vars
+end
+
+complete -c {bin} -n "__{fn_name}_meeting_env >/dev/null" -f -a "(env (__{fn_name}_meeting_env) {complete}=meetings {bin} 2>/dev/null)"
+"#,
+        bin = bin,
+        fn_name = bin.replace('-', "_"),
+        commands = MEETING_COMMANDS.join(" "),
+        commands_list = MEETING_COMMANDS.join(", "),
+        value_flags = value_flags.join(" "),
+        complete = COMPLETE_ENV,
+    )
+}
@@ -0,0 +1,318 @@
+use crate::cache;
+use crate::commands::search::DEFAULT_LIMIT;
+use crate::error::{GranolaError, Result};
+use crate::hooks::HooksConfig;
+use crate::models::{ConfigOutput, ConfigSetting};
+use crate::render::OutputFormat;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Settings that can be given in the config file.
+///
+/// ```toml
+/// [default]
+/// cache_path = "~/granola/cache-v3.json"
+/// limit = 10
+///
+/// [profiles.work]
+/// format = "text"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigValues {
+    pub cache_path: Option<PathBuf>,
+    pub json_errors: Option<bool>,
+    pub limit: Option<usize>,
+    pub format: Option<OutputFormat>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    default: ConfigValues,
+    #[serde(default)]
+    profiles: HashMap<String, ConfigValues>,
+    #[serde(default)]
+    hooks: HooksConfig,
+}
+
+/// Where an effective setting came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueSource {
+    Cli,
+    Env(&'static str),
+    /// The config file section, e.g. `default` or `profiles.work`
+    Config(String),
+    Default,
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::Cli => write!(f, "command line"),
+            ValueSource::Env(name) => write!(f, "env {}", name),
+            ValueSource::Config(section) => write!(f, "config [{}]", section),
+            ValueSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: ValueSource,
+}
+
+impl<T> Setting<T> {
+    /// Take the first value given, in precedence order: command line,
+    /// environment, config file, built-in default.
+    pub fn resolve(
+        cli: Option<T>,
+        env: Option<(T, &'static str)>,
+        config: Option<(T, String)>,
+        default: T,
+    ) -> Setting<T> {
+        if let Some(value) = cli {
+            return Setting {
+                value,
+                source: ValueSource::Cli,
+            };
+        }
+        if let Some((value, name)) = env {
+            return Setting {
+                value,
+                source: ValueSource::Env(name),
+            };
+        }
+        if let Some((value, section)) = config {
+            return Setting {
+                value,
+                source: ValueSource::Config(section),
+            };
+        }
+        Setting {
+            value: default,
+            source: ValueSource::Default,
+        }
+    }
+}
+
+/// Values given on the command line, which override everything else.
+#[derive(Debug, Default)]
+pub struct CliOverrides {
+    pub profile: Option<String>,
+    pub cache_path: Option<PathBuf>,
+    pub json_errors: bool,
+    pub limit: Option<usize>,
+    pub format: Option<OutputFormat>,
+}
+
+/// The effective configuration after merging all sources.
+#[derive(Debug)]
+pub struct Settings {
+    pub config_file: PathBuf,
+    pub config_file_exists: bool,
+    pub profile: Option<Setting<String>>,
+    pub cache_path: Setting<PathBuf>,
+    pub json_errors: Setting<bool>,
+    pub limit: Setting<usize>,
+    pub format: Setting<OutputFormat>,
+    pub hooks: HooksConfig,
+}
+
+impl Settings {
+    pub fn load(cli: CliOverrides) -> Result<Settings> {
+        let config_file = config_path();
+        let file = read_config(&config_file)?;
+        let config_file_exists = file.is_some();
+        let mut file = file.unwrap_or_default();
+
+        let profile = match cli.profile {
+            Some(name) => Some(Setting {
+                value: name,
+                source: ValueSource::Cli,
+            }),
+            None => std::env::var("GRANOLA_PROFILE").ok().map(|name| Setting {
+                value: name,
+                source: ValueSource::Env("GRANOLA_PROFILE"),
+            }),
+        };
+        let profile_values = match &profile {
+            Some(profile) => Some((
+                format!("profiles.{}", profile.value),
+                file.profiles.remove(&profile.value).ok_or_else(|| {
+                    GranolaError::InvalidArguments(format!(
+                        "Profile '{}' not found in {}",
+                        profile.value,
+                        config_file.display()
+                    ))
+                })?,
+            )),
+            None => None,
+        };
+        let layers = ConfigLayers {
+            default: file.default,
+            profile: profile_values,
+        };
+
+        let cache_path =
+            cache::resolve_cache_path(cli.cache_path, layers.get(|c| c.cache_path.clone()));
+        let json_errors = Setting::resolve(
+            cli.json_errors.then_some(true),
+            env_value("GRANOLA_JSON_ERRORS", parse_bool)?,
+            layers.get(|c| c.json_errors),
+            false,
+        );
+        let limit = Setting::resolve(
+            cli.limit,
+            env_value("GRANOLA_LIMIT", |s| s.parse().ok())?,
+            layers.get(|c| c.limit),
+            DEFAULT_LIMIT,
+        );
+        let format = Setting::resolve(
+            cli.format,
+            env_value("GRANOLA_FORMAT", |s| OutputFormat::from_str(s, true).ok())?,
+            layers.get(|c| c.format),
+            OutputFormat::Json,
+        );
+
+        Ok(Settings {
+            config_file,
+            config_file_exists,
+            profile,
+            cache_path,
+            json_errors,
+            limit,
+            format,
+            hooks: file.hooks,
+        })
+    }
+
+    /// The effective configuration, for `config show`.
+    pub fn to_output(&self) -> ConfigOutput {
+        let setting = |key: &str, value: serde_json::Value, source: &ValueSource| ConfigSetting {
+            key: key.to_string(),
+            value,
+            source: source.to_string(),
+        };
+
+        let format = self
+            .format
+            .value
+            .to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default();
+
+        ConfigOutput {
+            config_file: self.config_file.display().to_string(),
+            config_file_exists: self.config_file_exists,
+            profile: self.profile.as_ref().map(|p| p.value.clone()),
+            settings: vec![
+                setting(
+                    "cache_path",
+                    self.cache_path.value.display().to_string().into(),
+                    &self.cache_path.source,
+                ),
+                setting(
+                    "json_errors",
+                    self.json_errors.value.into(),
+                    &self.json_errors.source,
+                ),
+                setting("limit", self.limit.value.into(), &self.limit.source),
+                setting("format", format.into(), &self.format.source),
+            ],
+        }
+    }
+}
+
+/// The config file sections that apply, with the selected profile's values
+/// overriding the default section.
+struct ConfigLayers {
+    default: ConfigValues,
+    /// Section name and values of the selected profile
+    profile: Option<(String, ConfigValues)>,
+}
+
+impl ConfigLayers {
+    /// A value and the section it was found in.
+    fn get<T>(&self, field: impl Fn(&ConfigValues) -> Option<T>) -> Option<(T, String)> {
+        self.profile
+            .as_ref()
+            .and_then(|(section, values)| field(values).map(|v| (v, section.clone())))
+            .or_else(|| field(&self.default).map(|v| (v, "default".to_string())))
+    }
+}
+
+/// `GRANOLA_CONFIG`, else `$XDG_CONFIG_HOME/granola/config.toml`, else
+/// `~/.config/granola/config.toml`.
+pub fn config_path() -> PathBuf {
+    if let Ok(path) = std::env::var("GRANOLA_CONFIG") {
+        return PathBuf::from(path);
+    }
+
+    let config_home = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(".config")
+        });
+    config_home.join("granola").join("config.toml")
+}
+
+/// The parsed config file, or `None` when there isn't one.
+fn read_config(path: &Path) -> Result<Option<ConfigFile>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut file: ConfigFile = toml::from_str(&contents).map_err(|e| {
+        GranolaError::InvalidArguments(format!(
+            "Invalid config file {}: {}",
+            path.display(),
+            e.message()
+        ))
+    })?;
+
+    // `~` is not expanded by TOML, but is what people write
+    for values in std::iter::once(&mut file.default).chain(file.profiles.values_mut()) {
+        if let Some(path) = &values.cache_path {
+            values.cache_path = Some(expand_home(path));
+        }
+    }
+    Ok(Some(file))
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), std::env::var("HOME")) {
+        (Ok(rest), Ok(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn env_value<T>(
+    name: &'static str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Result<Option<(T, &'static str)>> {
+    match std::env::var(name) {
+        Ok(raw) => parse(&raw).map(|value| Some((value, name))).ok_or_else(|| {
+            GranolaError::InvalidArguments(format!("Invalid value '{}' for {}", raw, name))
+        }),
+        Err(_) => Ok(None),
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" => Some(true),
+        "0" | "false" | "no" => Some(false),
+        _ => None,
+    }
+}
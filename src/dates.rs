@@ -0,0 +1,25 @@
+use crate::error::{GranolaError, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Parse a date given on the command line, either `YYYY-MM-DD` (midnight UTC)
+/// or a full RFC 3339 timestamp.
+pub fn parse_date_arg(input: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+    }
+    DateTime::parse_from_rfc3339(input)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|_| {
+            GranolaError::InvalidArguments(format!(
+                "Invalid date '{}': expected YYYY-MM-DD or an RFC 3339 timestamp",
+                input
+            ))
+        })
+}
+
+/// Parse an ISO-8601 timestamp from the cache.
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
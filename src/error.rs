@@ -1,3 +1,4 @@
+use crate::models::{ErrorDetail, ErrorOutput};
 use std::fmt;
 
 #[derive(Debug)]
@@ -6,10 +7,12 @@ pub enum GranolaError {
     InvalidCacheFormat(String),
     MeetingNotFound(String),
     TranscriptNotFound(String),
-    #[allow(dead_code)]
+    /// Meeting ID and the requested section heading
+    SectionNotFound(String, String),
     InvalidArguments(String),
     IoError(std::io::Error),
     JsonError(serde_json::Error),
+    DatabaseError(rusqlite::Error),
 }
 
 impl fmt::Display for GranolaError {
@@ -27,11 +30,15 @@ impl fmt::Display for GranolaError {
             GranolaError::TranscriptNotFound(id) => {
                 write!(f, "Error: Transcript not found for meeting\nID: {}\nSuggestion: This meeting may not have been transcribed", id)
             }
+            GranolaError::SectionNotFound(id, section) => {
+                write!(f, "Error: Section not found in meeting notes\nID: {}\nSection: {}\nSuggestion: Use 'granola documents <id> --outline' to list the note's headings", id, section)
+            }
             GranolaError::InvalidArguments(msg) => {
                 write!(f, "Error: Invalid arguments\nDetails: {}", msg)
             }
             GranolaError::IoError(e) => write!(f, "Error: IO error\nDetails: {}", e),
             GranolaError::JsonError(e) => write!(f, "Error: JSON parsing error\nDetails: {}", e),
+            GranolaError::DatabaseError(e) => write!(f, "Error: Database error\nDetails: {}", e),
         }
     }
 }
@@ -44,9 +51,20 @@ impl From<std::io::Error> for GranolaError {
     }
 }
 
+impl From<rusqlite::Error> for GranolaError {
+    fn from(err: rusqlite::Error) -> Self {
+        GranolaError::DatabaseError(err)
+    }
+}
+
 impl From<serde_json::Error> for GranolaError {
     fn from(err: serde_json::Error) -> Self {
-        GranolaError::JsonError(err)
+        // Failures writing serialized output are IO errors, not bad JSON
+        if err.is_io() {
+            GranolaError::IoError(err.into())
+        } else {
+            GranolaError::JsonError(err)
+        }
     }
 }
 
@@ -57,21 +75,23 @@ impl GranolaError {
             GranolaError::InvalidCacheFormat(_) => 3,
             GranolaError::MeetingNotFound(_) => 4,
             GranolaError::TranscriptNotFound(_) => 4,
+            GranolaError::SectionNotFound(..) => 4,
             GranolaError::InvalidArguments(_) => 5,
             GranolaError::IoError(_) => 1,
             GranolaError::JsonError(_) => 3,
+            GranolaError::DatabaseError(_) => 1,
         }
     }
 
-    pub fn to_json(&self) -> serde_json::Value {
-        serde_json::json!({
-            "error": {
-                "code": self.exit_code(),
-                "type": self.error_type(),
-                "message": self.error_message(),
-                "suggestion": self.suggestion(),
-            }
-        })
+    pub fn to_json(&self) -> ErrorOutput {
+        ErrorOutput {
+            error: ErrorDetail {
+                code: self.exit_code(),
+                error_type: self.error_type().to_string(),
+                message: self.error_message(),
+                suggestion: self.suggestion(),
+            },
+        }
     }
 
     fn error_type(&self) -> &str {
@@ -80,9 +100,11 @@ impl GranolaError {
             GranolaError::InvalidCacheFormat(_) => "invalid_cache_format",
             GranolaError::MeetingNotFound(_) => "meeting_not_found",
             GranolaError::TranscriptNotFound(_) => "transcript_not_found",
+            GranolaError::SectionNotFound(..) => "section_not_found",
             GranolaError::InvalidArguments(_) => "invalid_arguments",
             GranolaError::IoError(_) => "io_error",
             GranolaError::JsonError(_) => "json_error",
+            GranolaError::DatabaseError(_) => "database_error",
         }
     }
 
@@ -94,9 +116,16 @@ impl GranolaError {
             GranolaError::TranscriptNotFound(id) => {
                 format!("Transcript not found for meeting: {}", id)
             }
+            GranolaError::SectionNotFound(id, section) => {
+                format!(
+                    "Section '{}' not found in notes for meeting: {}",
+                    section, id
+                )
+            }
             GranolaError::InvalidArguments(msg) => format!("Invalid arguments: {}", msg),
             GranolaError::IoError(e) => format!("IO error: {}", e),
             GranolaError::JsonError(e) => format!("JSON parsing error: {}", e),
+            GranolaError::DatabaseError(e) => format!("Database error: {}", e),
         }
     }
 
@@ -114,6 +143,9 @@ impl GranolaError {
             GranolaError::TranscriptNotFound(_) => {
                 Some("This meeting may not have been transcribed".to_string())
             }
+            GranolaError::SectionNotFound(..) => Some(
+                "Use 'granola documents <id> --outline' to list the note's headings".to_string(),
+            ),
             _ => None,
         }
     }
@@ -0,0 +1,543 @@
+use crate::commands;
+use crate::error::{GranolaError, Result};
+use crate::models::{Cache, WatchEventKind};
+use crate::watch::{CacheWatcher, Snapshot, WatchOptions};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The `[[hooks.<event>]]` tables of the config file.
+///
+/// ```toml
+/// [[hooks.on_new_meeting]]
+/// command = "notify-send \"$(jq -r .title)\""
+///
+/// [[hooks.on_transcript_complete]]
+/// url = "http://localhost:8080/granola"
+/// folder = "Sales"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HooksConfig {
+    #[serde(default)]
+    pub on_new_meeting: Vec<HookConfig>,
+    #[serde(default)]
+    pub on_transcript_complete: Vec<HookConfig>,
+    #[serde(default)]
+    pub on_notes_updated: Vec<HookConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HookConfig {
+    /// Identifies the hook in the state file and logs; defaults to the event
+    /// and position, e.g. `on_new_meeting.0`
+    pub name: Option<String>,
+    /// Shell command run with the meeting JSON on stdin
+    pub command: Option<String>,
+    /// URL the meeting JSON is POSTed to
+    pub url: Option<String>,
+    /// Only meetings in this folder
+    pub folder: Option<String>,
+    /// Only meetings with a participant whose name or email contains this
+    pub participant: Option<String>,
+    /// Only meetings whose title matches this regex
+    pub title: Option<String>,
+    /// Further attempts after a failed delivery
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    /// Wait before the first retry, doubling after each one
+    #[serde(default = "default_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+    /// How long one attempt may take before it is abandoned and retried
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+fn default_retry_delay_ms() -> u64 {
+    1000
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HookEvent {
+    #[serde(rename = "on_new_meeting")]
+    NewMeeting,
+    #[serde(rename = "on_transcript_complete")]
+    TranscriptComplete,
+    #[serde(rename = "on_notes_updated")]
+    NotesUpdated,
+}
+
+impl HookEvent {
+    fn as_str(self) -> &'static str {
+        match self {
+            HookEvent::NewMeeting => "on_new_meeting",
+            HookEvent::TranscriptComplete => "on_transcript_complete",
+            HookEvent::NotesUpdated => "on_notes_updated",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HookOptions {
+    pub state_file: PathBuf,
+    /// How long a transcript must stop growing to count as complete
+    pub transcript_idle: Duration,
+    pub watch: WatchOptions,
+    /// Process the current cache once and exit instead of watching
+    pub once: bool,
+}
+
+/// A configured hook, validated and with its filters compiled.
+struct Hook {
+    id: String,
+    event: HookEvent,
+    action: HookAction,
+    folder: Option<String>,
+    participant: Option<String>,
+    title: Option<Regex>,
+    retries: u32,
+    retry_delay: Duration,
+    timeout: Duration,
+}
+
+enum HookAction {
+    Command(String),
+    Post(String),
+}
+
+impl Hook {
+    fn compile(event: HookEvent, index: usize, config: &HookConfig) -> Result<Hook> {
+        let id = config
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("{}.{}", event.as_str(), index));
+        let action = match (&config.command, &config.url) {
+            (Some(command), None) => HookAction::Command(command.clone()),
+            (None, Some(url)) => HookAction::Post(url.clone()),
+            _ => {
+                return Err(GranolaError::InvalidArguments(format!(
+                    "Hook '{}' needs exactly one of 'command' or 'url'",
+                    id
+                )))
+            }
+        };
+        let title = config
+            .title
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| {
+                GranolaError::InvalidArguments(format!(
+                    "Invalid title regex in hook '{}': {}",
+                    id, e
+                ))
+            })?;
+
+        Ok(Hook {
+            id,
+            event,
+            action,
+            folder: config.folder.clone(),
+            participant: config.participant.as_deref().map(str::to_lowercase),
+            title,
+            retries: config.retries,
+            retry_delay: Duration::from_millis(config.retry_delay_ms),
+            timeout: Duration::from_secs(config.timeout_secs),
+        })
+    }
+
+    fn matches(&self, meeting_id: &str, cache: &Cache) -> bool {
+        let Some(doc) = cache.documents.get(meeting_id) else {
+            return false;
+        };
+
+        if let Some(folder) = &self.folder {
+            let in_folder = cache
+                .folders
+                .get(meeting_id)
+                .is_some_and(|folders| folders.iter().any(|f| f.eq_ignore_ascii_case(folder)));
+            if !in_folder {
+                return false;
+            }
+        }
+
+        if let Some(participant) = &self.participant {
+            let people = doc.people.as_ref();
+            let mut everyone = people.and_then(|p| p.creator.as_ref()).into_iter().chain(
+                people
+                    .and_then(|p| p.attendees.as_ref())
+                    .into_iter()
+                    .flatten(),
+            );
+            let found = everyone.any(|person| {
+                person.name.to_lowercase().contains(participant)
+                    || person
+                        .email
+                        .as_ref()
+                        .is_some_and(|email| email.to_lowercase().contains(participant))
+            });
+            if !found {
+                return false;
+            }
+        }
+
+        self.title.as_ref().is_none_or(|re| re.is_match(&doc.title))
+    }
+
+    /// Run the hook once, returning why it failed.
+    fn deliver(&self, meeting_id: &str, payload: &[u8]) -> std::result::Result<(), String> {
+        match &self.action {
+            HookAction::Command(command) => {
+                let mut child = Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .env("GRANOLA_HOOK_EVENT", self.event.as_str())
+                    .env("GRANOLA_MEETING_ID", meeting_id)
+                    .stdin(Stdio::piped())
+                    // Its own process group, so a timeout can stop everything
+                    // the shell started
+                    .process_group(0)
+                    .spawn()
+                    .map_err(|e| e.to_string())?;
+                // Write from a thread so a command that never reads its input
+                // can't block us past the timeout; ignoring the input is fine
+                if let Some(mut stdin) = child.stdin.take() {
+                    let payload = payload.to_vec();
+                    thread::spawn(move || {
+                        let _ = stdin.write_all(&payload);
+                    });
+                }
+
+                let deadline = Instant::now() + self.timeout;
+                loop {
+                    if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+                        return if status.success() {
+                            Ok(())
+                        } else {
+                            Err(format!("command exited with {}", status))
+                        };
+                    }
+                    if Instant::now() >= deadline {
+                        let _ = Command::new("kill")
+                            .args(["-KILL", "--", &format!("-{}", child.id())])
+                            .status();
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        return Err(format!("command timed out after {:?}", self.timeout));
+                    }
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+            HookAction::Post(url) => {
+                let agent: ureq::Agent = ureq::Agent::config_builder()
+                    .timeout_global(Some(self.timeout))
+                    .build()
+                    .into();
+                agent
+                    .post(url)
+                    .header("Content-Type", "application/json")
+                    .header("X-Granola-Event", self.event.as_str())
+                    .send(payload)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+/// What has been seen and what is still to be delivered, kept between runs so
+/// events aren't lost across restarts. A delivery leaves `pending` only after
+/// its attempts finish, so a crash mid-delivery repeats it: at least once.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HookState {
+    snapshot: Snapshot,
+    /// Transcript growth, for spotting when a transcript stops growing
+    transcripts: HashMap<String, TranscriptProgress>,
+    /// Deliveries queued but not yet made
+    pending: Vec<Delivery>,
+    /// Deliveries that ran out of attempts, queued again on the next run
+    #[serde(default)]
+    failed: Vec<Delivery>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TranscriptProgress {
+    segments: usize,
+    /// When the segment count last changed
+    since: DateTime<Utc>,
+    complete: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Delivery {
+    hook: String,
+    event: HookEvent,
+    meeting_id: String,
+}
+
+/// Deliver hooks for meeting changes, watching the cache until stopped (or
+/// once, with `options.once`).
+pub fn run(cache_path: &Path, config: &HooksConfig, options: &HookOptions) -> Result<()> {
+    let hooks = compile_hooks(config)?;
+    if hooks.is_empty() {
+        return Err(GranolaError::InvalidArguments(
+            "No hooks configured; add [[hooks.on_new_meeting]] (or another event) to the config file"
+                .to_string(),
+        ));
+    }
+
+    let mut watcher = CacheWatcher::new(cache_path, options.watch);
+    let mut cache = watcher.load()?;
+    let mut state = match read_state(&options.state_file)? {
+        Some(state) => state,
+        None => {
+            // Nothing to compare against yet: remember what is there now
+            // rather than firing for every meeting in the cache
+            let state = baseline(&cache);
+            write_state(&options.state_file, &state)?;
+            eprintln!(
+                "Recorded {} existing meetings in {}",
+                state.snapshot.meetings.len(),
+                options.state_file.display()
+            );
+            state
+        }
+    };
+    let failed = std::mem::take(&mut state.failed);
+    state.pending.extend(failed);
+
+    loop {
+        queue_deliveries(&mut state, &hooks, &cache, options.transcript_idle);
+        write_state(&options.state_file, &state)?;
+        deliver_pending(&mut state, &hooks, &cache, &options.state_file)?;
+
+        if options.once {
+            return Ok(());
+        }
+        thread::sleep(options.watch.interval);
+        if let Some(next) = watcher.poll() {
+            cache = next;
+        }
+    }
+}
+
+fn compile_hooks(config: &HooksConfig) -> Result<Vec<Hook>> {
+    let groups = [
+        (HookEvent::NewMeeting, &config.on_new_meeting),
+        (
+            HookEvent::TranscriptComplete,
+            &config.on_transcript_complete,
+        ),
+        (HookEvent::NotesUpdated, &config.on_notes_updated),
+    ];
+    let mut hooks = Vec::new();
+    for (event, configs) in groups {
+        for (index, config) in configs.iter().enumerate() {
+            hooks.push(Hook::compile(event, index, config)?);
+        }
+    }
+    Ok(hooks)
+}
+
+fn baseline(cache: &Cache) -> HookState {
+    let now = Utc::now();
+    let snapshot = Snapshot::of(cache);
+    // Existing transcripts count as complete, whatever their age
+    let transcripts = snapshot
+        .meetings
+        .iter()
+        .filter(|(_, m)| m.transcript_segments > 0)
+        .map(|(id, m)| {
+            let progress = TranscriptProgress {
+                segments: m.transcript_segments,
+                since: now,
+                complete: true,
+            };
+            (id.clone(), progress)
+        })
+        .collect();
+    HookState {
+        snapshot,
+        transcripts,
+        pending: Vec::new(),
+        failed: Vec::new(),
+    }
+}
+
+/// Compare the cache with the state and queue a delivery for each matching
+/// hook of each new event.
+fn queue_deliveries(state: &mut HookState, hooks: &[Hook], cache: &Cache, idle: Duration) {
+    let now = Utc::now();
+    let next = Snapshot::of(cache);
+    let mut fired: Vec<(HookEvent, String)> = Vec::new();
+
+    for change in state.snapshot.changes(&next, cache) {
+        match change.event {
+            WatchEventKind::MeetingCreated => {
+                fired.push((HookEvent::NewMeeting, change.meeting_id))
+            }
+            WatchEventKind::NotesChanged => {
+                fired.push((HookEvent::NotesUpdated, change.meeting_id))
+            }
+            WatchEventKind::MeetingUpdated | WatchEventKind::TranscriptExtended => {}
+        }
+    }
+
+    // A transcript is complete once it has stopped growing for a while
+    let mut ids: Vec<&String> = next.meetings.keys().collect();
+    ids.sort();
+    for id in ids {
+        let segments = next.meetings[id].transcript_segments;
+        if segments == 0 {
+            continue;
+        }
+        let progress = state
+            .transcripts
+            .entry(id.clone())
+            .or_insert(TranscriptProgress {
+                segments,
+                since: now,
+                complete: false,
+            });
+        if progress.segments != segments {
+            progress.segments = segments;
+            progress.since = now;
+            progress.complete = false;
+        }
+        let idle_for = (now - progress.since).to_std().unwrap_or_default();
+        if !progress.complete && idle_for >= idle {
+            progress.complete = true;
+            fired.push((HookEvent::TranscriptComplete, id.clone()));
+        }
+    }
+
+    for (event, meeting_id) in fired {
+        for hook in hooks {
+            if hook.event == event && hook.matches(&meeting_id, cache) {
+                state.pending.push(Delivery {
+                    hook: hook.id.clone(),
+                    event,
+                    meeting_id: meeting_id.clone(),
+                });
+            }
+        }
+    }
+    state.snapshot = next;
+}
+
+/// Make each queued delivery, retrying failures, and drop it from the state
+/// once it succeeds. Deliveries that run out of attempts move to `failed`.
+fn deliver_pending(
+    state: &mut HookState,
+    hooks: &[Hook],
+    cache: &Cache,
+    state_file: &Path,
+) -> Result<()> {
+    while let Some(delivery) = state.pending.first().cloned() {
+        // Hooks removed from the config since the delivery was queued are
+        // dropped
+        let delivered = match hooks.iter().find(|h| h.id == delivery.hook) {
+            Some(hook) => attempt(hook, &delivery, cache),
+            None => true,
+        };
+        state.pending.remove(0);
+        if !delivered {
+            state.failed.push(delivery);
+        }
+        write_state(state_file, state)?;
+    }
+    Ok(())
+}
+
+/// Run a hook with retries. Returns false only when every attempt failed; a
+/// meeting that has left the cache can't be delivered, so it counts as done.
+fn attempt(hook: &Hook, delivery: &Delivery, cache: &Cache) -> bool {
+    let payload = match commands::get_meeting_details(&delivery.meeting_id, cache) {
+        Ok(details) => serde_json::to_vec(&details).expect("output types always serialize"),
+        Err(_) => {
+            eprintln!(
+                "Skipping hook '{}': meeting {} is no longer in the cache",
+                hook.id, delivery.meeting_id
+            );
+            return true;
+        }
+    };
+
+    let mut delay = hook.retry_delay;
+    for attempt in 0..=hook.retries {
+        match hook.deliver(&delivery.meeting_id, &payload) {
+            Ok(()) => {
+                eprintln!(
+                    "Hook '{}' ran for {} ({})",
+                    hook.id,
+                    delivery.meeting_id,
+                    delivery.event.as_str()
+                );
+                return true;
+            }
+            Err(e) if attempt < hook.retries => {
+                eprintln!("Hook '{}' failed, retrying in {:?}: {}", hook.id, delay, e);
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            Err(e) => eprintln!(
+                "Hook '{}' failed for {}, giving up after {} attempts until the next run: {}",
+                hook.id,
+                delivery.meeting_id,
+                hook.retries + 1,
+                e
+            ),
+        }
+    }
+    false
+}
+
+/// `$XDG_STATE_HOME/granola/hooks-state.json`, else
+/// `~/.local/state/granola/hooks-state.json`.
+pub fn default_state_file() -> PathBuf {
+    let state_home = std::env::var("XDG_STATE_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(".local").join("state")
+        });
+    state_home.join("granola").join("hooks-state.json")
+}
+
+fn read_state(path: &Path) -> Result<Option<HookState>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(serde_json::from_str(&contents)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Write to a temporary file and rename it over the old one, so a crash
+/// never leaves a half-written state behind.
+fn write_state(path: &Path, state: &HookState) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(state)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
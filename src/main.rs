@@ -1,11 +1,39 @@
 mod cache;
 mod commands;
+mod completions;
+mod config;
+mod dates;
 mod error;
+mod hooks;
+mod mcp;
 mod models;
+mod notes;
+mod render;
+mod serve;
+mod speakers;
+mod tui;
+mod watch;
 
-use clap::{Parser, Subcommand};
-use error::Result;
+use clap::{ArgGroup, Args, CommandFactory, Parser, Subcommand};
+use commands::actions::ActionOptions;
+use commands::documents::NotesFormat;
+use commands::export::{ExportFilters, ExportOptions};
+use commands::export_sqlite::SqliteExportOptions;
+use commands::schema::SchemaTarget;
+use commands::transcript::{TimeOffset, TranscriptOptions};
+use completions::CompletionShell;
+use config::{CliOverrides, Settings};
+use error::{GranolaError, Result};
+use hooks::HookOptions;
+use models::CacheInfo;
+use render::projection::Projection;
+use render::version::SchemaVersion;
+use render::{OutputFormat, RenderOptions};
+use speakers::SpeakerMapping;
+use std::io::{ErrorKind, Write};
 use std::path::PathBuf;
+use std::time::Duration;
+use watch::WatchOptions;
 
 #[derive(Parser)]
 #[command(name = "granola")]
@@ -17,13 +45,41 @@ struct Cli {
     #[command(subcommand)]
     command: Commands,
 
-    /// Path to cache file (overrides env var and default)
+    /// Path to cache file (overrides env var, config and default)
     #[arg(long, global = true)]
     cache_path: Option<PathBuf>,
 
+    /// Config profile to use from the config file
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     /// Output errors as JSON to stdout (default: stderr)
     #[arg(long, global = true)]
     json_errors: bool,
+
+    /// Output format [default: json]
+    #[arg(long, global = true, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Columns to include with --format csv (comma-separated)
+    #[arg(long, global = true, value_delimiter = ',')]
+    columns: Option<Vec<String>>,
+
+    /// Only include these dotted field paths in JSON output (e.g. results.id,results.title)
+    #[arg(long, global = true, value_delimiter = ',')]
+    fields: Option<Vec<String>>,
+
+    /// Print JSON on a single line instead of pretty-printing
+    #[arg(long, global = true)]
+    compact: bool,
+
+    /// Wrap JSON output in a versioned envelope with cache metadata
+    #[arg(long, global = true)]
+    envelope: bool,
+
+    /// JSON output layout to produce (older versions drop fields added since)
+    #[arg(long, global = true, value_enum, default_value_t = SchemaVersion::CURRENT)]
+    schema_version: SchemaVersion,
 }
 
 #[derive(Subcommand)]
@@ -33,9 +89,9 @@ enum Commands {
         /// Search query
         query: String,
 
-        /// Maximum number of results
-        #[arg(long, default_value = "30")]
-        limit: usize,
+        /// Maximum number of results [default: 30]
+        #[arg(long)]
+        limit: Option<usize>,
     },
 
     /// Get meeting metadata
@@ -48,27 +104,272 @@ enum Commands {
     Transcript {
         /// Meeting ID
         meeting_id: String,
+
+        /// Start of the time window (seconds, mm:ss or hh:mm:ss; negative counts from the end)
+        #[arg(long, allow_hyphen_values = true)]
+        from: Option<String>,
+
+        /// End of the time window (seconds, mm:ss or hh:mm:ss; negative counts from the end)
+        #[arg(long, allow_hyphen_values = true)]
+        to: Option<String>,
+
+        /// Merge consecutive segments from the same speaker into turns
+        #[arg(long)]
+        merge_turns: bool,
+
+        /// Only include segments from this speaker (raw source or mapped name)
+        #[arg(long)]
+        speaker: Option<String>,
+
+        #[command(flatten)]
+        speaker_names: SpeakerNameArgs,
+    },
+
+    /// Get per-speaker talk time and conversation statistics
+    Stats {
+        /// Meeting ID
+        meeting_id: String,
+
+        #[command(flatten)]
+        speaker_names: SpeakerNameArgs,
     },
 
     /// Get meeting notes/documents
     Documents {
         /// Meeting ID
         meeting_id: String,
+
+        /// Notes format to return (default: plain, or markdown with --format markdown)
+        #[arg(long, value_enum, conflicts_with_all = ["outline", "section"])]
+        notes_format: Option<NotesFormat>,
+
+        /// Return the heading tree of the notes instead of their content
+        #[arg(long, conflicts_with = "section")]
+        outline: bool,
+
+        /// Return only the notes under this heading (e.g. "Next steps")
+        #[arg(long)]
+        section: Option<String>,
+    },
+
+    /// Extract action items from notes, overviews and optionally transcripts
+    Actions {
+        /// Only include meetings on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only include items assigned to this person (case-insensitive substring)
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Only include items that are not completed
+        #[arg(long)]
+        open: bool,
+
+        /// Also look for commitments ("I'll ...", "action item") in transcripts
+        #[arg(long)]
+        transcripts: bool,
+    },
+
+    /// Write meetings to a directory of Markdown files or a SQLite database
+    #[command(group(ArgGroup::new("destination").required(true).args(["dir", "sqlite"])))]
+    Export {
+        /// Directory to write the files to (created if missing)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// SQLite database to create or update, with meetings, people, segments and notes tables and full-text search
+        #[arg(long)]
+        sqlite: Option<PathBuf>,
+
+        /// File name template [default: {date}-{slug}.md, or "{date} {title}.md" with --obsidian]; placeholders: {id}, {title}, {slug}, {date}, {year}, {month}, {type}
+        #[arg(long, conflicts_with = "sqlite")]
+        filename: Option<String>,
+
+        /// Only meetings on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only meetings before this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Only meetings matching this search query
+        #[arg(long)]
+        query: Option<String>,
+
+        /// Include the transcript after the notes
+        #[arg(long, conflicts_with = "sqlite")]
+        transcript: bool,
+
+        /// Only rewrite meetings that changed since the last export
+        #[arg(long)]
+        incremental: bool,
+
+        /// Write an Obsidian vault: meeting and person notes linked with [[wikilinks]]
+        #[arg(long, conflicts_with = "sqlite")]
+        obsidian: bool,
+    },
+
+    /// Serve meeting data over HTTP, reloading when the cache changes
+    Serve {
+        /// Address to listen on (use 0.0.0.0 to expose beyond this machine)
+        #[arg(long, default_value = "127.0.0.1:7777")]
+        addr: String,
+    },
+
+    /// Run as an MCP server over stdio for AI assistants
+    Mcp,
+
+    /// Stream meeting changes as NDJSON events while the cache is updated
+    Watch {
+        /// Milliseconds between checks of the cache file
+        #[arg(long, default_value = "1000")]
+        interval: u64,
+
+        /// Milliseconds the file must stay unchanged before it is read
+        #[arg(long, default_value = "500")]
+        debounce: u64,
+    },
+
+    /// Browse meetings interactively in the terminal
+    Tui,
+
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
+
+    /// Run the hooks from the config file when meetings change
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
     },
 
     /// Show usage patterns (for AI assistants)
     Workflow,
+
+    /// Print the JSON Schema of a command's output (all commands when omitted)
+    Schema {
+        /// Command whose output schema to print
+        #[arg(value_enum)]
+        command: Option<SchemaTarget>,
+    },
+}
+
+impl Commands {
+    /// Whether the command depends on the config file or environment.
+    fn reads_settings(&self) -> bool {
+        !matches!(
+            self,
+            Commands::Workflow | Commands::Completions { .. } | Commands::Schema { .. }
+        )
+    }
+}
+
+#[derive(Subcommand)]
+enum HooksAction {
+    /// Watch the cache and deliver hooks until stopped
+    Run {
+        /// Deliver hooks for changes since the last run, then exit
+        #[arg(long)]
+        once: bool,
+
+        /// File recording delivered events [default: ~/.local/state/granola/hooks-state.json]
+        #[arg(long)]
+        state_file: Option<PathBuf>,
+
+        /// Seconds a transcript must stop growing before on_transcript_complete fires
+        #[arg(long, default_value = "300")]
+        transcript_idle: u64,
+
+        /// Milliseconds between checks of the cache file
+        #[arg(long, default_value = "1000")]
+        interval: u64,
+
+        /// Milliseconds the file must stay unchanged before it is read
+        #[arg(long, default_value = "500")]
+        debounce: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the effective configuration and where each value came from
+    Show,
+}
+
+#[derive(Args)]
+struct SpeakerNameArgs {
+    /// Name speakers after the meeting's creator and attendees
+    #[arg(long)]
+    map_speakers: bool,
+
+    /// JSON file mapping raw speaker sources to names (e.g. {"system": "Bob"})
+    #[arg(long)]
+    speaker_map: Option<PathBuf>,
+}
+
+impl SpeakerNameArgs {
+    fn into_mapping(self) -> Result<SpeakerMapping> {
+        Ok(SpeakerMapping {
+            from_participants: self.map_speakers,
+            names: match &self.speaker_map {
+                Some(path) => SpeakerMapping::load_names(path)?,
+                None => Default::default(),
+            },
+        })
+    }
 }
 
 fn main() {
+    // Completion scripts call back into the binary to list meeting IDs
+    if std::env::var_os(completions::COMPLETE_ENV).is_some() {
+        std::process::exit(completions::complete_meetings());
+    }
+
     let cli = Cli::parse();
-    let json_errors = cli.json_errors;
 
-    let result = run(cli);
+    // Renderers write straight to stdout so large outputs stream as they are produced
+    let mut stdout = std::io::stdout().lock();
+
+    let (result, json_errors) = if cli.command.reads_settings() {
+        // A broken config still has to be reported, honouring the flag at least
+        let settings = Settings::load(CliOverrides {
+            profile: cli.profile.clone(),
+            cache_path: cli.cache_path.clone(),
+            json_errors: cli.json_errors,
+            limit: match cli.command {
+                Commands::Search { limit, .. } => limit,
+                _ => None,
+            },
+            format: cli.format,
+        });
+        let json_errors = match &settings {
+            Ok(settings) => settings.json_errors.value,
+            Err(_) => cli.json_errors,
+        };
+        let result = settings.and_then(|settings| run(cli, &settings, &mut stdout));
+        (result, json_errors)
+    } else {
+        let json_errors = cli.json_errors;
+        (run_without_settings(cli, &mut stdout), json_errors)
+    };
 
     match result {
-        Ok(output) => {
-            println!("{}", output);
+        Ok(()) => {
+            std::process::exit(0);
+        }
+        // The reader went away (e.g. piped into `head`), nothing left to report
+        Err(GranolaError::IoError(e)) if e.kind() == ErrorKind::BrokenPipe => {
             std::process::exit(0);
         }
         Err(e) => {
@@ -82,34 +383,229 @@ fn main() {
     }
 }
 
-fn run(cli: Cli) -> Result<String> {
-    // Handle workflow command separately (no cache needed)
-    if matches!(cli.command, Commands::Workflow) {
-        return Ok(commands::get_workflow_guide());
+/// Commands that need neither settings nor the cache, so they keep working
+/// with a broken config file.
+fn run_without_settings(cli: Cli, out: &mut dyn Write) -> Result<()> {
+    match cli.command {
+        Commands::Workflow => {
+            writeln!(out, "{}", commands::get_workflow_guide())?;
+            Ok(())
+        }
+        Commands::Completions { shell } => {
+            completions::write_completions(shell, &mut Cli::command(), out)
+        }
+        // Schemas describe the output types, not any particular cache
+        Commands::Schema { command } => {
+            serde_json::to_writer_pretty(&mut *out, &commands::get_schema(command))?;
+            writeln!(out)?;
+            Ok(())
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn run(cli: Cli, settings: &Settings, out: &mut dyn Write) -> Result<()> {
+    let format = settings.format.value;
+    let render_options = |envelope| RenderOptions {
+        columns: cli.columns,
+        fields: cli.fields.as_deref().map(Projection::parse),
+        compact: cli.compact,
+        schema_version: cli.schema_version,
+        envelope,
+    };
+
+    if let Commands::Config {
+        action: ConfigAction::Show,
+    } = cli.command
+    {
+        let renderer = format.renderer(render_options(None))?;
+        return renderer.config(&settings.to_output(), out);
+    }
+
+    let cache_path = settings.cache_path.value.clone();
+
+    // The server loads (and reloads) the cache itself
+    if let Commands::Serve { addr } = &cli.command {
+        return serve::serve(cache_path, addr);
+    }
+    if matches!(cli.command, Commands::Mcp) {
+        return mcp::run(cache_path);
+    }
+    if let Commands::Hooks {
+        action:
+            HooksAction::Run {
+                once,
+                state_file,
+                transcript_idle,
+                interval,
+                debounce,
+            },
+    } = cli.command
+    {
+        let options = HookOptions {
+            state_file: state_file.unwrap_or_else(hooks::default_state_file),
+            transcript_idle: Duration::from_secs(transcript_idle),
+            watch: WatchOptions {
+                interval: Duration::from_millis(interval),
+                debounce: Duration::from_millis(debounce),
+            },
+            once,
+        };
+        return hooks::run(&cache_path, &settings.hooks, &options);
+    }
+    if let Commands::Watch { interval, debounce } = cli.command {
+        let options = WatchOptions {
+            interval: Duration::from_millis(interval),
+            debounce: Duration::from_millis(debounce),
+        };
+        // Flush every batch so consumers see events as they happen
+        return watch::watch(&cache_path, options, |events, _| {
+            for event in events {
+                serde_json::to_writer(&mut *out, event)?;
+                writeln!(out)?;
+            }
+            Ok(out.flush()?)
+        });
     }
 
     // Load cache for other commands
-    let cache_path = cache::resolve_cache_path(cli.cache_path);
     let cache = cache::load_cache(&cache_path)?;
 
+    let envelope = cli.envelope.then(|| CacheInfo {
+        path: cache_path.display().to_string(),
+        mtime: cache::cache_modified(&cache_path)
+            .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+    });
+    let renderer = format.renderer(render_options(envelope))?;
+
     // Dispatch to appropriate command
     match cli.command {
-        Commands::Search { query, limit } => {
-            let output = commands::search_meetings(&query, limit, &cache)?;
-            Ok(serde_json::to_string_pretty(&output)?)
+        Commands::Search { query, .. } => {
+            let output = commands::search_meetings(&query, settings.limit.value, &cache)?;
+            renderer.search(&output, out)
         }
         Commands::Details { meeting_id } => {
             let output = commands::get_meeting_details(&meeting_id, &cache)?;
-            Ok(serde_json::to_string_pretty(&output)?)
+            renderer.details(&output, out)
+        }
+        Commands::Transcript {
+            meeting_id,
+            from,
+            to,
+            merge_turns,
+            speaker,
+            speaker_names,
+        } => {
+            let options = TranscriptOptions {
+                from: from.as_deref().map(TimeOffset::parse).transpose()?,
+                to: to.as_deref().map(TimeOffset::parse).transpose()?,
+                merge_turns,
+                speaker,
+                speaker_mapping: speaker_names.into_mapping()?,
+            };
+            let output = commands::get_transcript(&meeting_id, &options, &cache)?;
+            renderer.transcript(&output, out)
+        }
+        Commands::Stats {
+            meeting_id,
+            speaker_names,
+        } => {
+            let mapping = speaker_names.into_mapping()?;
+            let output = commands::get_transcript_stats(&meeting_id, &mapping, &cache)?;
+            renderer.stats(&output, out)
         }
-        Commands::Transcript { meeting_id } => {
-            let output = commands::get_transcript(&meeting_id, &cache)?;
-            Ok(serde_json::to_string_pretty(&output)?)
+        Commands::Documents {
+            meeting_id,
+            outline: true,
+            ..
+        } => {
+            let output = commands::get_notes_outline(&meeting_id, &cache)?;
+            renderer.outline(&output, out)
+        }
+        Commands::Documents {
+            meeting_id,
+            section: Some(section),
+            ..
+        } => {
+            let output = commands::get_notes_section(&meeting_id, &section, &cache)?;
+            renderer.documents(&output, out)
+        }
+        Commands::Documents {
+            meeting_id,
+            notes_format,
+            ..
+        } => {
+            // Markdown output reads best with the notes' own headings and lists
+            let notes_format = notes_format.unwrap_or(if format == OutputFormat::Markdown {
+                NotesFormat::Markdown
+            } else {
+                NotesFormat::Plain
+            });
+            let output = commands::get_documents(&meeting_id, notes_format, &cache)?;
+            renderer.documents(&output, out)
+        }
+        Commands::Tui => tui::run(&cache),
+        Commands::Actions {
+            since,
+            owner,
+            open,
+            transcripts,
+        } => {
+            let options = ActionOptions {
+                since: since.as_deref().map(dates::parse_date_arg).transpose()?,
+                owner,
+                open_only: open,
+                include_transcripts: transcripts,
+            };
+            let output = commands::find_action_items(&options, &cache)?;
+            renderer.actions(&output, out)
+        }
+        Commands::Export {
+            dir,
+            sqlite,
+            filename,
+            since,
+            until,
+            query,
+            transcript,
+            incremental,
+            obsidian,
+        } => {
+            let filters = ExportFilters {
+                since: since.as_deref().map(dates::parse_date_arg).transpose()?,
+                until: until.as_deref().map(dates::parse_date_arg).transpose()?,
+                query,
+            };
+            let output = if let Some(path) = sqlite {
+                let options = SqliteExportOptions {
+                    path,
+                    filters,
+                    incremental,
+                };
+                commands::export_sqlite(&options, &cache)?
+            } else {
+                let options = ExportOptions {
+                    // clap requires one of --dir and --sqlite
+                    dir: dir.expect("--dir or --sqlite is required"),
+                    filename,
+                    filters,
+                    include_transcript: transcript,
+                    incremental,
+                    obsidian,
+                };
+                commands::export_markdown(&options, &cache)?
+            };
+            renderer.export(&output, out)
         }
-        Commands::Documents { meeting_id } => {
-            let output = commands::get_documents(&meeting_id, &cache)?;
-            Ok(serde_json::to_string_pretty(&output)?)
+        Commands::Workflow
+        | Commands::Schema { .. }
+        | Commands::Completions { .. }
+        | Commands::Config { .. }
+        | Commands::Serve { .. }
+        | Commands::Mcp
+        | Commands::Watch { .. }
+        | Commands::Hooks { .. } => {
+            unreachable!()
         }
-        Commands::Workflow => unreachable!(),
     }
 }
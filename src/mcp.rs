@@ -0,0 +1,320 @@
+use crate::cache::LiveCache;
+use crate::commands;
+use crate::commands::documents::NotesFormat;
+use crate::commands::schema::{schema_for, SchemaTarget};
+use crate::commands::search::DEFAULT_LIMIT;
+use crate::commands::transcript::{TimeOffset, TranscriptOptions};
+use crate::error::{GranolaError, Result};
+use crate::models::Cache;
+use crate::speakers::SpeakerMapping;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+/// MCP revisions this server understands, newest first
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const RESOURCE_PREFIX: &str = "granola://meetings/";
+
+// JSON-RPC error codes
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const PARSE_ERROR: i64 = -32700;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+#[derive(Deserialize, JsonSchema)]
+struct SearchArgs {
+    /// Text to look for in titles, participants and summaries
+    query: String,
+    /// Maximum number of results (default 30)
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct MeetingArgs {
+    /// Meeting ID from search_meetings
+    meeting_id: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct TranscriptArgs {
+    /// Meeting ID from search_meetings
+    meeting_id: String,
+    /// Start of the time window (seconds, mm:ss or hh:mm:ss; negative counts from the end)
+    from: Option<String>,
+    /// End of the time window (seconds, mm:ss or hh:mm:ss; negative counts from the end)
+    to: Option<String>,
+    /// Merge consecutive segments from the same speaker into turns
+    #[serde(default)]
+    merge_turns: bool,
+    /// Only include segments from this speaker
+    speaker: Option<String>,
+    /// Name speakers after the meeting's creator and attendees
+    #[serde(default)]
+    map_speakers: bool,
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct DocumentsArgs {
+    /// Meeting ID from search_meetings
+    meeting_id: String,
+    /// Notes format to return (default plain)
+    notes_format: Option<NotesFormat>,
+    /// Only return the notes under this heading, e.g. "Next steps"
+    section: Option<String>,
+}
+
+struct Tool {
+    name: &'static str,
+    description: &'static str,
+    input_schema: fn() -> Value,
+    output: SchemaTarget,
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "search_meetings",
+        description: "Find meetings by title, participant or summary, newest first",
+        input_schema: schema_for::<SearchArgs>,
+        output: SchemaTarget::Search,
+    },
+    Tool {
+        name: "get_meeting_details",
+        description: "Get a meeting's metadata: date, duration, participants",
+        input_schema: schema_for::<MeetingArgs>,
+        output: SchemaTarget::Details,
+    },
+    Tool {
+        name: "get_transcript",
+        description: "Get a meeting's transcript, optionally a time window or merged into turns",
+        input_schema: schema_for::<TranscriptArgs>,
+        output: SchemaTarget::Transcript,
+    },
+    Tool {
+        name: "get_documents",
+        description: "Get a meeting's notes and AI overview, more compact than the transcript",
+        input_schema: schema_for::<DocumentsArgs>,
+        output: SchemaTarget::Documents,
+    },
+];
+
+/// Speak the Model Context Protocol over stdin/stdout until stdin closes.
+pub fn run(cache_path: PathBuf) -> Result<()> {
+    let mut cache = LiveCache::load(cache_path)?;
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout().lock();
+
+    // Messages are newline-delimited JSON-RPC
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        cache.refresh();
+        if let Some(response) = handle_message(&line, cache.cache()) {
+            serde_json::to_writer(&mut stdout, &response)?;
+            writeln!(stdout)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn handle_message(line: &str, cache: &Cache) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+    };
+
+    // Notifications have no ID and get no response
+    let id = message.get("id")?.clone();
+    let method = message["method"].as_str().unwrap_or("");
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(initialize(&params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(list_tools()),
+        "tools/call" => call_tool(&params, cache),
+        "resources/list" => Ok(list_resources(cache)),
+        "resources/templates/list" => Ok(list_resource_templates()),
+        "resources/read" => read_resource(&params, cache),
+        _ => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn initialize(params: &Value) -> Value {
+    // Agree on the client's version when we support it, else offer our newest
+    let requested = params["protocolVersion"].as_str().unwrap_or("");
+    let version = PROTOCOL_VERSIONS
+        .iter()
+        .find(|v| **v == requested)
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {}, "resources": {} },
+        "serverInfo": { "name": "granola", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Search meetings first, then fetch details, documents or transcripts by meeting ID. Prefer documents over transcripts; they are much smaller.",
+    })
+}
+
+fn list_tools() -> Value {
+    let tools: Vec<Value> = TOOLS
+        .iter()
+        .map(|tool| {
+            json!({
+                "name": tool.name,
+                "description": tool.description,
+                "inputSchema": (tool.input_schema)(),
+                "outputSchema": tool.output.schema(),
+            })
+        })
+        .collect();
+    json!({ "tools": tools })
+}
+
+fn call_tool(params: &Value, cache: &Cache) -> std::result::Result<Value, (i64, String)> {
+    let name = params["name"].as_str().unwrap_or("");
+    let args = params.get("arguments").cloned().unwrap_or(json!({}));
+
+    let output = match name {
+        "search_meetings" => parse_args(args).and_then(|a: SearchArgs| {
+            to_value(commands::search_meetings(
+                &a.query,
+                a.limit.unwrap_or(DEFAULT_LIMIT),
+                cache,
+            ))
+        }),
+        "get_meeting_details" => parse_args(args).and_then(|a: MeetingArgs| {
+            to_value(commands::get_meeting_details(&a.meeting_id, cache))
+        }),
+        "get_transcript" => parse_args(args).and_then(|a: TranscriptArgs| {
+            let options = TranscriptOptions {
+                from: a.from.as_deref().map(TimeOffset::parse).transpose()?,
+                to: a.to.as_deref().map(TimeOffset::parse).transpose()?,
+                merge_turns: a.merge_turns,
+                speaker: a.speaker,
+                speaker_mapping: a
+                    .map_speakers
+                    .then(SpeakerMapping::from_participants)
+                    .unwrap_or_default(),
+            };
+            to_value(commands::get_transcript(&a.meeting_id, &options, cache))
+        }),
+        "get_documents" => parse_args(args).and_then(|a: DocumentsArgs| match a.section {
+            Some(section) => to_value(commands::get_notes_section(&a.meeting_id, &section, cache)),
+            None => to_value(commands::get_documents(
+                &a.meeting_id,
+                a.notes_format.unwrap_or(NotesFormat::Plain),
+                cache,
+            )),
+        }),
+        _ => return Err((INVALID_PARAMS, format!("Unknown tool: {}", name))),
+    };
+
+    // Command failures are tool results the model can read, not protocol errors
+    Ok(match output {
+        Ok(value) => json!({
+            "content": [{ "type": "text", "text": value.to_string() }],
+            "structuredContent": value,
+            "isError": false,
+        }),
+        Err(e) => json!({
+            "content": [{ "type": "text", "text": serde_json::to_string(&e.to_json()).unwrap_or_default() }],
+            "isError": true,
+        }),
+    })
+}
+
+fn parse_args<T: DeserializeOwned>(args: Value) -> Result<T> {
+    serde_json::from_value(args).map_err(|e| GranolaError::InvalidArguments(e.to_string()))
+}
+
+fn to_value<T: serde::Serialize>(output: Result<T>) -> Result<Value> {
+    Ok(serde_json::to_value(output?)?)
+}
+
+fn list_resources(cache: &Cache) -> Value {
+    let mut docs: Vec<_> = cache.documents.values().collect();
+    docs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    let resources: Vec<Value> = docs
+        .iter()
+        .map(|doc| {
+            json!({
+                "uri": format!("{}{}", RESOURCE_PREFIX, doc.id),
+                "name": doc.title,
+                "description": format!("Meeting on {}", doc.created_at),
+                "mimeType": "application/json",
+            })
+        })
+        .collect();
+    json!({ "resources": resources })
+}
+
+fn list_resource_templates() -> Value {
+    json!({
+        "resourceTemplates": [
+            {
+                "uriTemplate": format!("{}{{meeting_id}}", RESOURCE_PREFIX),
+                "name": "Meeting details",
+                "mimeType": "application/json",
+            },
+            {
+                "uriTemplate": format!("{}{{meeting_id}}/transcript", RESOURCE_PREFIX),
+                "name": "Meeting transcript",
+                "mimeType": "application/json",
+            },
+            {
+                "uriTemplate": format!("{}{{meeting_id}}/documents", RESOURCE_PREFIX),
+                "name": "Meeting notes and overview",
+                "mimeType": "application/json",
+            },
+        ]
+    })
+}
+
+fn read_resource(params: &Value, cache: &Cache) -> std::result::Result<Value, (i64, String)> {
+    let uri = params["uri"].as_str().unwrap_or("");
+    let path = uri
+        .strip_prefix(RESOURCE_PREFIX)
+        .ok_or_else(|| (INVALID_PARAMS, format!("Unknown resource: {}", uri)))?;
+
+    let output = match path.split_once('/') {
+        None => to_value(commands::get_meeting_details(path, cache)),
+        Some((id, "transcript")) => to_value(commands::get_transcript(
+            id,
+            &TranscriptOptions::default(),
+            cache,
+        )),
+        Some((id, "documents")) => to_value(commands::get_documents(id, NotesFormat::Plain, cache)),
+        Some(_) => return Err((INVALID_PARAMS, format!("Unknown resource: {}", uri))),
+    }
+    .map_err(|e| (RESOURCE_NOT_FOUND, e.to_json().error.message))?;
+
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": "application/json",
+            "text": output.to_string(),
+        }]
+    }))
+}
@@ -1,3 +1,5 @@
+use crate::notes::NoteNode;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,12 +15,30 @@ pub struct CacheDocument {
     pub updated_at: String,
     #[serde(rename = "type")]
     pub doc_type: Option<String>,
+    /// Structured notes as a ProseMirror document tree
+    pub notes: Option<serde_json::Value>,
     pub notes_plain: Option<String>,
     pub notes_markdown: Option<String>,
     pub overview: Option<String>,
     pub people: Option<DocumentPeople>,
 }
 
+impl CacheDocument {
+    /// Structured notes, when present and non-empty.
+    pub fn structured_notes(&self) -> Option<NoteNode> {
+        self.notes
+            .as_ref()
+            .and_then(NoteNode::parse)
+            .filter(|n| !n.to_plain().trim().is_empty())
+    }
+
+    pub fn has_notes(&self) -> bool {
+        self.notes_plain.is_some()
+            || self.notes_markdown.is_some()
+            || self.structured_notes().is_some()
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct DocumentPeople {
     #[allow(dead_code)]
@@ -44,63 +64,116 @@ pub struct TranscriptSegment {
 // Output Structures (optimized for JSON output)
 // ============================================================================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SearchOutput {
     pub query: String,
     pub total_matches: usize,
     pub results: Vec<SearchResult>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SearchResult {
     pub id: String,
     pub title: String,
+    /// ISO-8601 creation time
     pub date: String,
     pub participants: Vec<String>,
+    /// AI-generated overview
     pub summary: Option<String>,
     pub has_transcript: bool,
     pub has_notes: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct MeetingDetails {
     pub id: String,
     pub title: String,
+    /// ISO-8601 creation time
     pub date: String,
     pub duration_minutes: Option<i32>,
     pub participants: Vec<ParticipantInfo>,
+    /// Granola document type, "unknown" when missing
     #[serde(rename = "type")]
     pub meeting_type: String,
     pub has_transcript: bool,
     pub has_notes: bool,
+    /// ISO-8601
     pub created_at: String,
+    /// ISO-8601
     pub updated_at: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ParticipantInfo {
     pub name: String,
     pub email: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct TranscriptOutput {
     pub id: String,
     pub title: String,
     pub duration_seconds: Option<i64>,
+    /// Effective time window, only present with --from/--to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub window: Option<TranscriptWindow>,
+    /// In order of first appearance
     pub speakers: Vec<String>,
     pub total_segments: usize,
     pub segments: Vec<CompactSegment>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TranscriptWindow {
+    pub from_seconds: i64,
+    pub to_seconds: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CompactSegment {
-    pub s: String, // speaker
-    pub t: String, // text
-    pub ts: i64,   // timestamp
+    /// Speaker
+    pub s: String,
+    /// Text
+    pub t: String,
+    /// Timestamp in seconds from the start of the meeting
+    pub ts: i64,
+    /// End timestamp (merged turns only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub te: Option<i64>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TranscriptStats {
+    pub id: String,
+    pub title: String,
+    pub duration_seconds: Option<i64>,
+    pub total_segments: usize,
+    pub total_talk_time_seconds: i64,
+    pub total_silence_seconds: i64,
+    pub speakers: Vec<SpeakerStats>,
+    pub silence_gaps: Vec<SilenceGap>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SpeakerStats {
+    pub speaker: String,
+    pub talk_time_seconds: i64,
+    pub talk_time_percent: f64,
+    pub word_count: usize,
+    pub segment_count: usize,
+    pub turn_count: usize,
+    pub longest_monologue_seconds: i64,
+    pub interruptions: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SilenceGap {
+    pub start: i64,
+    pub end: i64,
+    pub duration_seconds: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct DocumentsOutput {
     pub id: String,
     pub title: String,
@@ -108,18 +181,164 @@ pub struct DocumentsOutput {
     pub documents: Vec<Document>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct Document {
     pub id: String,
     pub title: String,
+    /// "meeting_notes" or "overview"
     #[serde(rename = "type")]
     pub doc_type: String,
+    /// "plain" or "markdown", describing the content actually returned
     pub format: String,
     pub content: String,
     pub word_count: usize,
+    /// ISO-8601
     pub created_at: String,
 }
 
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NotesOutline {
+    pub id: String,
+    pub title: String,
+    /// Top-level headings of the notes, with subheadings nested under them
+    pub headings: Vec<OutlineHeading>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct OutlineHeading {
+    /// 1 for `#`, 2 for `##`, ...
+    pub level: usize,
+    pub title: String,
+    pub children: Vec<OutlineHeading>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ActionsOutput {
+    pub total_items: usize,
+    pub items: Vec<ActionItem>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ActionItem {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    /// ISO-8601 creation time of the meeting
+    pub date: String,
+    pub text: String,
+    /// Best guess at the owner, from mentions or the meeting's participants
+    pub assignee: Option<String>,
+    /// Whether the item was ticked off in a checklist
+    pub completed: bool,
+    /// "notes", "overview" or "transcript"
+    pub source: String,
+    /// Seconds from the start of the meeting, for transcript items
+    #[serde(rename = "ts", skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ExportOutput {
+    /// Where the meetings were written
+    pub destination: String,
+    pub total_meetings: usize,
+    pub written: usize,
+    /// Skipped by `--incremental` because they had not changed
+    pub unchanged: usize,
+    pub files: Vec<ExportedFile>,
+    /// Files from earlier exports of meetings whose file name changed,
+    /// relative to the destination
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_files: Vec<String>,
+    /// Person notes written by `--obsidian`, relative to the destination
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub people_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ExportedFile {
+    pub meeting_id: String,
+    /// Relative to the destination; absent for `--sqlite`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// "written" or "unchanged"
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ConfigOutput {
+    pub config_file: String,
+    pub config_file_exists: bool,
+    /// Selected with --profile or GRANOLA_PROFILE
+    pub profile: Option<String>,
+    pub settings: Vec<ConfigSetting>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ConfigSetting {
+    pub key: String,
+    pub value: serde_json::Value,
+    /// "command line", "env <VAR>", "config [<section>]" or "default"
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchEventKind {
+    MeetingCreated,
+    MeetingUpdated,
+    TranscriptExtended,
+    NotesChanged,
+}
+
+/// One line of `watch` output.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WatchEvent {
+    pub event: WatchEventKind,
+    pub meeting_id: String,
+    pub title: String,
+    /// ISO-8601 `updated_at` of the meeting after the change
+    pub updated_at: String,
+    /// Transcript segments after the change
+    pub transcript_segments: usize,
+    /// Transcript segments before the change, for `transcript_extended`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_transcript_segments: Option<usize>,
+}
+
+/// Wrapper written around command output with `--envelope`
+#[derive(Debug, Serialize)]
+pub struct Envelope<T> {
+    pub schema_version: String,
+    pub command: String,
+    /// ISO-8601 time the output was produced
+    pub generated_at: String,
+    pub cache: CacheInfo,
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheInfo {
+    pub path: String,
+    /// ISO-8601 modification time of the cache file
+    pub mtime: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ErrorOutput {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ErrorDetail {
+    /// Process exit code
+    pub code: i32,
+    /// Machine-readable error type, e.g. "meeting_not_found"
+    #[serde(rename = "type")]
+    pub error_type: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
 // ============================================================================
 // Cache Container
 // ============================================================================
@@ -128,4 +347,6 @@ pub struct Document {
 pub struct Cache {
     pub documents: HashMap<String, CacheDocument>,
     pub transcripts: HashMap<String, Vec<TranscriptSegment>>,
+    /// Titles of the folders each document is in, by document ID
+    pub folders: HashMap<String, Vec<String>>,
 }
@@ -0,0 +1,241 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// A node of Granola's structured notes, a ProseMirror document tree.
+#[derive(Debug, Deserialize)]
+pub struct NoteNode {
+    #[serde(rename = "type")]
+    pub node_type: String,
+    #[serde(default)]
+    pub attrs: Value,
+    #[serde(default)]
+    pub content: Vec<NoteNode>,
+    pub text: Option<String>,
+    #[serde(default)]
+    pub marks: Vec<NoteMark>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NoteMark {
+    #[serde(rename = "type")]
+    pub mark_type: String,
+    #[serde(default)]
+    pub attrs: Value,
+}
+
+impl NoteNode {
+    /// Parse the raw `notes` value from the cache, if it is a document tree.
+    pub fn parse(value: &Value) -> Option<NoteNode> {
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        render_blocks(&self.content, true)
+    }
+
+    pub fn to_plain(&self) -> String {
+        render_blocks(&self.content, false)
+    }
+}
+
+fn render_blocks(nodes: &[NoteNode], markdown: bool) -> String {
+    nodes
+        .iter()
+        .map(|n| render_block(n, markdown))
+        .filter(|b| !b.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn render_block(node: &NoteNode, markdown: bool) -> String {
+    match node.node_type.as_str() {
+        "heading" => {
+            let text = render_inline(&node.content, markdown);
+            if markdown {
+                let level = node.attrs["level"].as_u64().unwrap_or(1).clamp(1, 6);
+                format!("{} {}", "#".repeat(level as usize), text)
+            } else {
+                text
+            }
+        }
+        "bulletList" | "orderedList" | "taskList" => render_list(node, markdown),
+        "blockquote" => prefix_lines(&render_blocks(&node.content, markdown), "> "),
+        "codeBlock" => {
+            let code = render_inline(&node.content, false);
+            if markdown {
+                let language = node.attrs["language"].as_str().unwrap_or("");
+                format!("```{}\n{}\n```", language, code)
+            } else {
+                code
+            }
+        }
+        "horizontalRule" => {
+            if markdown {
+                "---".to_string()
+            } else {
+                String::new()
+            }
+        }
+        "paragraph" => render_inline(&node.content, markdown),
+        // Unknown containers still contribute their text
+        _ if node.text.is_some() => render_inline(std::slice::from_ref(node), markdown),
+        _ => render_blocks(&node.content, markdown),
+    }
+}
+
+fn render_list(list: &NoteNode, markdown: bool) -> String {
+    let start = list.attrs["start"].as_u64().unwrap_or(1);
+
+    list.content
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let mut marker = if list.node_type == "orderedList" {
+                format!("{}. ", start + i as u64)
+            } else {
+                "- ".to_string()
+            };
+            // Task items carry their state as a `checked` attribute
+            if let Some(checked) = item.attrs["checked"].as_bool() {
+                marker.push_str(if checked { "[x] " } else { "[ ] " });
+            }
+            render_list_item(item, &marker, markdown)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The first block follows the marker; later blocks and nested lists are
+/// indented to line up with it.
+fn render_list_item(item: &NoteNode, marker: &str, markdown: bool) -> String {
+    let indent = " ".repeat(marker.len());
+    let blocks: Vec<String> = item
+        .content
+        .iter()
+        .map(|n| render_block(n, markdown))
+        .filter(|b| !b.trim().is_empty())
+        .collect();
+
+    let mut out = marker.to_string();
+    for (i, block) in blocks.iter().enumerate() {
+        for (j, line) in block.lines().enumerate() {
+            if i > 0 || j > 0 {
+                out.push('\n');
+                if !line.is_empty() {
+                    out.push_str(&indent);
+                }
+            }
+            out.push_str(line);
+        }
+    }
+    out.trim_end().to_string()
+}
+
+fn render_inline(nodes: &[NoteNode], markdown: bool) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node.node_type.as_str() {
+            "hardBreak" => out.push('\n'),
+            "text" => out.push_str(&apply_marks(node, markdown)),
+            _ => out.push_str(&render_inline(&node.content, markdown)),
+        }
+    }
+    out
+}
+
+fn apply_marks(node: &NoteNode, markdown: bool) -> String {
+    let mut text = node.text.clone().unwrap_or_default();
+    if text.is_empty() {
+        return text;
+    }
+
+    for mark in &node.marks {
+        let href = mark.attrs["href"].as_str();
+        text = match (mark.mark_type.as_str(), markdown) {
+            ("bold" | "strong", true) => format!("**{}**", text),
+            ("italic" | "em", true) => format!("*{}*", text),
+            ("strike", true) => format!("~~{}~~", text),
+            ("code", true) => format!("`{}`", text),
+            ("link", true) => match href {
+                Some(href) => format!("[{}]({})", text, href),
+                None => text,
+            },
+            ("link", false) => match href {
+                Some(href) if href != text => format!("{} ({})", text, href),
+                _ => text,
+            },
+            _ => text,
+        };
+    }
+    text
+}
+
+fn prefix_lines(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", prefix, line).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A Markdown ATX heading (`## Title`) and the line it starts on.
+#[derive(Debug)]
+pub struct MarkdownHeading {
+    pub level: usize,
+    pub title: String,
+    pub line: usize,
+}
+
+/// Headings in Markdown text, skipping `#` lines inside fenced code blocks.
+pub fn markdown_headings(markdown: &str) -> Vec<MarkdownHeading> {
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+
+    for (i, line) in markdown.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) {
+            let rest = &trimmed[level..];
+            // `#tag` is not a heading, `#` alone is an empty one
+            if rest.is_empty() || rest.starts_with(' ') {
+                headings.push(MarkdownHeading {
+                    level,
+                    title: rest.trim().trim_end_matches('#').trim_end().to_string(),
+                    line: i,
+                });
+            }
+        }
+    }
+
+    headings
+}
+
+/// The title and content of the first heading titled `name`
+/// (case-insensitive, ignoring a trailing colon). The content runs up to the
+/// next heading of the same or a higher level, so subsections are included;
+/// the heading line itself is not.
+pub fn markdown_section(markdown: &str, name: &str) -> Option<(String, String)> {
+    let normalize = |s: &str| s.trim().trim_end_matches(':').trim().to_lowercase();
+    let wanted = normalize(name);
+
+    let headings = markdown_headings(markdown);
+    let index = headings
+        .iter()
+        .position(|h| normalize(&h.title) == wanted)?;
+    let heading = &headings[index];
+    let end = headings[index + 1..]
+        .iter()
+        .find(|h| h.level <= heading.level)
+        .map(|h| h.line);
+
+    let lines: Vec<&str> = markdown.lines().collect();
+    let body = &lines[heading.line + 1..end.unwrap_or(lines.len())];
+    Some((heading.title.clone(), body.join("\n").trim().to_string()))
+}
@@ -0,0 +1,91 @@
+use super::Renderer;
+use crate::error::{GranolaError, Result};
+use crate::models::{SearchOutput, SearchResult};
+use std::io::Write;
+
+/// Columns available for search results, in default order
+const SEARCH_COLUMNS: &[&str] = &[
+    "id",
+    "title",
+    "date",
+    "participants",
+    "summary",
+    "has_transcript",
+    "has_notes",
+];
+
+/// RFC 4180 CSV with a header row.
+pub struct CsvRenderer {
+    /// Columns to include, in order; all columns when `None`
+    pub columns: Option<Vec<String>>,
+}
+
+impl Renderer for CsvRenderer {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn search(&self, output: &SearchOutput, w: &mut dyn Write) -> Result<()> {
+        let columns = self.select(SEARCH_COLUMNS)?;
+
+        write_record(w, columns.iter().map(|c| c.to_string()))?;
+        for result in &output.results {
+            write_record(w, columns.iter().map(|c| search_field(result, c)))?;
+        }
+        Ok(())
+    }
+}
+
+impl CsvRenderer {
+    fn select<'a>(&'a self, available: &[&'a str]) -> Result<Vec<&'a str>> {
+        let Some(columns) = &self.columns else {
+            return Ok(available.to_vec());
+        };
+
+        columns
+            .iter()
+            .map(|c| {
+                let c = c.trim();
+                if available.contains(&c) {
+                    Ok(c)
+                } else {
+                    Err(GranolaError::InvalidArguments(format!(
+                        "Unknown column '{}'. Available columns: {}",
+                        c,
+                        available.join(", ")
+                    )))
+                }
+            })
+            .collect()
+    }
+}
+
+fn search_field(result: &SearchResult, column: &str) -> String {
+    match column {
+        "id" => result.id.clone(),
+        "title" => result.title.clone(),
+        "date" => result.date.clone(),
+        "participants" => result.participants.join("; "),
+        "summary" => result.summary.clone().unwrap_or_default(),
+        "has_transcript" => result.has_transcript.to_string(),
+        "has_notes" => result.has_notes.to_string(),
+        _ => unreachable!("column validated against SEARCH_COLUMNS"),
+    }
+}
+
+fn write_record(w: &mut dyn Write, fields: impl Iterator<Item = String>) -> Result<()> {
+    let fields: Vec<String> = fields.map(|f| quote(&f)).collect();
+    // RFC 4180 records end with CRLF
+    write!(w, "{}\r\n", fields.join(","))?;
+    Ok(())
+}
+
+/// Quote a field when it contains a separator, quote or line break, doubling
+/// any embedded quotes.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,95 @@
+use super::projection::Projection;
+use super::version::SchemaVersion;
+use super::Renderer;
+use crate::error::Result;
+use crate::models::{
+    ActionsOutput, CacheInfo, ConfigOutput, DocumentsOutput, Envelope, ExportOutput,
+    MeetingDetails, NotesOutline, SearchOutput, TranscriptOutput, TranscriptStats,
+};
+use serde::Serialize;
+use std::io::Write;
+
+pub struct JsonRenderer {
+    pub fields: Option<Projection>,
+    pub compact: bool,
+    pub schema_version: SchemaVersion,
+    /// Wrap output in an envelope describing the cache it was read from
+    pub envelope: Option<CacheInfo>,
+}
+
+impl Renderer for JsonRenderer {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn search(&self, output: &SearchOutput, w: &mut dyn Write) -> Result<()> {
+        self.write("search", output, w)
+    }
+
+    fn details(&self, output: &MeetingDetails, w: &mut dyn Write) -> Result<()> {
+        self.write("details", output, w)
+    }
+
+    fn transcript(&self, output: &TranscriptOutput, w: &mut dyn Write) -> Result<()> {
+        self.write("transcript", output, w)
+    }
+
+    fn stats(&self, output: &TranscriptStats, w: &mut dyn Write) -> Result<()> {
+        self.write("stats", output, w)
+    }
+
+    fn documents(&self, output: &DocumentsOutput, w: &mut dyn Write) -> Result<()> {
+        self.write("documents", output, w)
+    }
+
+    fn outline(&self, output: &NotesOutline, w: &mut dyn Write) -> Result<()> {
+        self.write("outline", output, w)
+    }
+
+    fn actions(&self, output: &ActionsOutput, w: &mut dyn Write) -> Result<()> {
+        self.write("actions", output, w)
+    }
+
+    fn config(&self, output: &ConfigOutput, w: &mut dyn Write) -> Result<()> {
+        self.write("config", output, w)
+    }
+
+    fn export(&self, output: &ExportOutput, w: &mut dyn Write) -> Result<()> {
+        self.write("export", output, w)
+    }
+}
+
+impl JsonRenderer {
+    fn write<T: Serialize>(&self, command: &str, value: &T, w: &mut dyn Write) -> Result<()> {
+        let mut value = self
+            .schema_version
+            .downgrade(command, serde_json::to_value(value)?)?;
+        if let Some(fields) = &self.fields {
+            value = fields.apply(value);
+        }
+
+        match &self.envelope {
+            Some(cache) => {
+                let envelope = Envelope {
+                    schema_version: self.schema_version.as_str().to_string(),
+                    command: command.to_string(),
+                    generated_at: chrono::Utc::now().to_rfc3339(),
+                    cache: cache.clone(),
+                    data: value,
+                };
+                write_json(&envelope, self.compact, w)
+            }
+            None => write_json(&value, self.compact, w),
+        }
+    }
+}
+
+fn write_json<T: Serialize>(value: &T, compact: bool, w: &mut dyn Write) -> Result<()> {
+    if compact {
+        serde_json::to_writer(&mut *w, value)?;
+    } else {
+        serde_json::to_writer_pretty(&mut *w, value)?;
+    }
+    writeln!(w)?;
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use super::{format_date, format_setting, format_timestamp, yes_no, Renderer};
+use crate::error::Result;
+use crate::models::{
+    ActionsOutput, ConfigOutput, DocumentsOutput, ExportOutput, MeetingDetails, NotesOutline,
+    OutlineHeading, SearchOutput, TranscriptOutput, TranscriptStats,
+};
+use std::io::Write;
+
+pub struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {
+    fn name(&self) -> &'static str {
+        "markdown"
+    }
+
+    fn search(&self, output: &SearchOutput, w: &mut dyn Write) -> Result<()> {
+        let rows: Vec<Vec<String>> = output
+            .results
+            .iter()
+            .map(|r| {
+                vec![
+                    format!("`{}`", r.id),
+                    format_date(&r.date),
+                    r.title.clone(),
+                    r.participants.join(", "),
+                    yes_no(r.has_transcript).to_string(),
+                    yes_no(r.has_notes).to_string(),
+                ]
+            })
+            .collect();
+
+        writeln!(
+            w,
+            "# Search: {}\n\n{} result(s)\n\n{}",
+            output.query,
+            output.total_matches,
+            table(
+                &["ID", "Date", "Title", "Participants", "Transcript", "Notes"],
+                &rows
+            )
+        )?;
+        Ok(())
+    }
+
+    fn details(&self, output: &MeetingDetails, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "# {}\n", output.title)?;
+        writeln!(w, "- **ID:** `{}`", output.id)?;
+        writeln!(w, "- **Date:** {}", format_date(&output.date))?;
+        if let Some(minutes) = output.duration_minutes {
+            writeln!(w, "- **Duration:** {} min", minutes)?;
+        }
+        writeln!(w, "- **Type:** {}", output.meeting_type)?;
+        writeln!(w, "- **Transcript:** {}", yes_no(output.has_transcript))?;
+        writeln!(w, "- **Notes:** {}", yes_no(output.has_notes))?;
+
+        if !output.participants.is_empty() {
+            writeln!(w, "\n## Participants\n")?;
+            for p in &output.participants {
+                match &p.email {
+                    Some(email) => writeln!(w, "- {} <{}>", p.name, email)?,
+                    None => writeln!(w, "- {}", p.name)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn transcript(&self, output: &TranscriptOutput, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "# {}", output.title)?;
+        for segment in &output.segments {
+            writeln!(
+                w,
+                "\n**{}** [{}]: {}",
+                segment.s,
+                format_timestamp(segment.ts),
+                segment.t
+            )?;
+        }
+        Ok(())
+    }
+
+    fn stats(&self, output: &TranscriptStats, w: &mut dyn Write) -> Result<()> {
+        let rows: Vec<Vec<String>> = output
+            .speakers
+            .iter()
+            .map(|s| {
+                vec![
+                    s.speaker.clone(),
+                    format_timestamp(s.talk_time_seconds),
+                    format!("{}%", s.talk_time_percent),
+                    s.word_count.to_string(),
+                    s.turn_count.to_string(),
+                    format_timestamp(s.longest_monologue_seconds),
+                    s.interruptions.to_string(),
+                ]
+            })
+            .collect();
+
+        writeln!(
+            w,
+            "# {}\n\n{}\n\nSilence: {} across {} gap(s)",
+            output.title,
+            table(
+                &[
+                    "Speaker",
+                    "Talk time",
+                    "Share",
+                    "Words",
+                    "Turns",
+                    "Longest",
+                    "Interruptions"
+                ],
+                &rows
+            ),
+            format_timestamp(output.total_silence_seconds),
+            output.silence_gaps.len()
+        )?;
+        Ok(())
+    }
+
+    fn documents(&self, output: &DocumentsOutput, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "# {}", output.title)?;
+        for doc in &output.documents {
+            writeln!(w, "\n## {}\n\n{}", doc.title, doc.content.trim_end())?;
+        }
+        Ok(())
+    }
+
+    fn outline(&self, output: &NotesOutline, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "# {}\n", output.title)?;
+        write_headings(&output.headings, 0, w)
+    }
+
+    fn actions(&self, output: &ActionsOutput, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "# Action items")?;
+
+        // Items arrive grouped by meeting, newest first
+        let mut current_meeting = None;
+        for item in &output.items {
+            if current_meeting != Some(&item.meeting_id) {
+                writeln!(
+                    w,
+                    "\n## {} ({})\n",
+                    item.meeting_title,
+                    format_date(&item.date)
+                )?;
+                current_meeting = Some(&item.meeting_id);
+            }
+            let checkbox = if item.completed { "[x]" } else { "[ ]" };
+            match &item.assignee {
+                Some(assignee) => writeln!(w, "- {} {} — *{}*", checkbox, item.text, assignee)?,
+                None => writeln!(w, "- {} {}", checkbox, item.text)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn config(&self, output: &ConfigOutput, w: &mut dyn Write) -> Result<()> {
+        let rows: Vec<Vec<String>> = output
+            .settings
+            .iter()
+            .map(|s| {
+                vec![
+                    format!("`{}`", s.key),
+                    format_setting(&s.value),
+                    s.source.clone(),
+                ]
+            })
+            .collect();
+
+        writeln!(w, "# Configuration\n")?;
+        let exists = if output.config_file_exists {
+            ""
+        } else {
+            " (not found)"
+        };
+        writeln!(w, "- **Config file:** `{}`{}", output.config_file, exists)?;
+        if let Some(profile) = &output.profile {
+            writeln!(w, "- **Profile:** {}", profile)?;
+        }
+        writeln!(w, "\n{}", table(&["Key", "Value", "Source"], &rows))?;
+        Ok(())
+    }
+
+    fn export(&self, output: &ExportOutput, w: &mut dyn Write) -> Result<()> {
+        let rows: Vec<Vec<String>> = output
+            .files
+            .iter()
+            .map(|f| {
+                vec![
+                    format!("`{}`", f.meeting_id),
+                    f.path.clone().unwrap_or_default(),
+                    f.status.clone(),
+                ]
+            })
+            .collect();
+
+        writeln!(
+            w,
+            "# Export to `{}`\n\n{} meeting(s): {} written, {} unchanged\n\n{}",
+            output.destination,
+            output.total_meetings,
+            output.written,
+            output.unchanged,
+            table(&["Meeting", "Path", "Status"], &rows)
+        )?;
+        for (heading, paths) in [
+            ("Removed files", &output.removed_files),
+            ("Person notes", &output.people_files),
+        ] {
+            if !paths.is_empty() {
+                writeln!(w, "\n## {}\n", heading)?;
+                for path in paths {
+                    writeln!(w, "- {}", path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Headings as a nested bullet list.
+fn write_headings(headings: &[OutlineHeading], depth: usize, w: &mut dyn Write) -> Result<()> {
+    for heading in headings {
+        writeln!(w, "{}- {}", "  ".repeat(depth), heading.title)?;
+        write_headings(&heading.children, depth + 1, w)?;
+    }
+    Ok(())
+}
+
+fn table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut lines = vec![
+        format!("| {} |", headers.join(" | ")),
+        format!("|{}", "---|".repeat(headers.len())),
+    ];
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|c| escape_cell(c)).collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+    lines.join("\n")
+}
+
+/// Pipes and newlines would break the table layout.
+fn escape_cell(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', " ")
+}
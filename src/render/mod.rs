@@ -0,0 +1,183 @@
+pub mod csv;
+pub mod json;
+pub mod markdown;
+pub mod ndjson;
+pub mod projection;
+pub mod subtitles;
+pub mod text;
+pub mod version;
+
+use crate::error::{GranolaError, Result};
+use crate::models::{
+    ActionsOutput, CacheInfo, ConfigOutput, DocumentsOutput, ExportOutput, MeetingDetails,
+    NotesOutline, SearchOutput, TranscriptOutput, TranscriptStats,
+};
+use clap::ValueEnum;
+use projection::Projection;
+use serde::Deserialize;
+use std::io::Write;
+use version::SchemaVersion;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Pretty-printed JSON (default)
+    Json,
+    /// Plain text for reading in a terminal
+    Text,
+    /// Markdown for pasting into docs
+    Markdown,
+    /// Newline-delimited JSON, one result or segment per line
+    Ndjson,
+    /// RFC 4180 CSV (search only, see --columns)
+    Csv,
+    /// SubRip subtitles (transcript only)
+    Srt,
+    /// WebVTT subtitles with speakers as voice tags (transcript only)
+    Vtt,
+}
+
+/// Format-specific settings from the command line.
+#[derive(Debug)]
+pub struct RenderOptions {
+    /// CSV columns to include
+    pub columns: Option<Vec<String>>,
+    /// Dotted field paths to keep in JSON output
+    pub fields: Option<Projection>,
+    /// Write JSON on a single line instead of pretty-printing
+    pub compact: bool,
+    /// JSON layout to produce
+    pub schema_version: SchemaVersion,
+    /// Wrap JSON output in a versioned envelope
+    pub envelope: Option<CacheInfo>,
+}
+
+impl OutputFormat {
+    pub fn renderer(self, options: RenderOptions) -> Result<Box<dyn Renderer>> {
+        if options.fields.is_some() && !matches!(self, OutputFormat::Json | OutputFormat::Ndjson) {
+            return Err(GranolaError::InvalidArguments(
+                "--fields only applies to --format json and ndjson".to_string(),
+            ));
+        }
+
+        let versioned =
+            options.envelope.is_some() || options.schema_version != SchemaVersion::CURRENT;
+        if versioned && self != OutputFormat::Json {
+            return Err(GranolaError::InvalidArguments(
+                "--envelope and --schema-version only apply to --format json".to_string(),
+            ));
+        }
+
+        Ok(match self {
+            OutputFormat::Json => Box::new(json::JsonRenderer {
+                fields: options.fields,
+                compact: options.compact,
+                schema_version: options.schema_version,
+                envelope: options.envelope,
+            }),
+            OutputFormat::Text => Box::new(text::TextRenderer),
+            OutputFormat::Markdown => Box::new(markdown::MarkdownRenderer),
+            OutputFormat::Ndjson => Box::new(ndjson::NdjsonRenderer {
+                fields: options.fields,
+            }),
+            OutputFormat::Csv => Box::new(csv::CsvRenderer {
+                columns: options.columns,
+            }),
+            OutputFormat::Srt => Box::new(subtitles::SrtRenderer),
+            OutputFormat::Vtt => Box::new(subtitles::VttRenderer),
+        })
+    }
+}
+
+/// Writes command output to stdout.
+///
+/// Each output format implements this trait. Formats that only make sense for
+/// some commands can leave the other methods at their default, which reports
+/// the combination as unsupported.
+pub trait Renderer {
+    /// Name used in error messages, matching the `--format` value
+    fn name(&self) -> &'static str;
+
+    fn search(&self, _output: &SearchOutput, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "search"))
+    }
+
+    fn details(&self, _output: &MeetingDetails, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "details"))
+    }
+
+    fn transcript(&self, _output: &TranscriptOutput, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "transcript"))
+    }
+
+    fn stats(&self, _output: &TranscriptStats, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "stats"))
+    }
+
+    fn documents(&self, _output: &DocumentsOutput, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "documents"))
+    }
+
+    fn outline(&self, _output: &NotesOutline, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "documents --outline"))
+    }
+
+    fn actions(&self, _output: &ActionsOutput, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "actions"))
+    }
+
+    fn config(&self, _output: &ConfigOutput, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "config"))
+    }
+
+    fn export(&self, _output: &ExportOutput, _w: &mut dyn Write) -> Result<()> {
+        Err(unsupported(self.name(), "export"))
+    }
+}
+
+fn unsupported(format: &str, command: &str) -> GranolaError {
+    GranolaError::InvalidArguments(format!(
+        "--format {} is not supported by the {} command",
+        format, command
+    ))
+}
+
+/// Format seconds from the start of a meeting as `mm:ss` or `h:mm:ss`.
+pub fn format_timestamp(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    if seconds >= 3600 {
+        format!(
+            "{}:{:02}:{:02}",
+            seconds / 3600,
+            (seconds % 3600) / 60,
+            seconds % 60
+        )
+    } else {
+        format!("{:02}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// Format an ISO-8601 timestamp as `YYYY-MM-DD HH:MM`, falling back to the
+/// raw value when it cannot be parsed.
+pub fn format_date(date: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(date)
+        .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+/// Format a config value for display: strings without quotes, anything
+/// else as JSON.
+pub fn format_setting(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(v) => v.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
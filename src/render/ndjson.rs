@@ -0,0 +1,79 @@
+use super::projection::Projection;
+use super::Renderer;
+use crate::error::Result;
+use crate::models::{
+    ActionsOutput, DocumentsOutput, MeetingDetails, NotesOutline, SearchOutput, TranscriptOutput,
+    TranscriptStats,
+};
+use serde::Serialize;
+use std::io::Write;
+
+/// Newline-delimited JSON: collections are streamed one item per line, single
+/// objects are written as one compact line.
+pub struct NdjsonRenderer {
+    pub fields: Option<Projection>,
+}
+
+impl Renderer for NdjsonRenderer {
+    fn name(&self) -> &'static str {
+        "ndjson"
+    }
+
+    fn search(&self, output: &SearchOutput, w: &mut dyn Write) -> Result<()> {
+        self.write_lines("results", &output.results, w)
+    }
+
+    fn details(&self, output: &MeetingDetails, w: &mut dyn Write) -> Result<()> {
+        write_line(output, self.fields.as_ref(), w)
+    }
+
+    fn transcript(&self, output: &TranscriptOutput, w: &mut dyn Write) -> Result<()> {
+        self.write_lines("segments", &output.segments, w)
+    }
+
+    fn stats(&self, output: &TranscriptStats, w: &mut dyn Write) -> Result<()> {
+        write_line(output, self.fields.as_ref(), w)
+    }
+
+    fn documents(&self, output: &DocumentsOutput, w: &mut dyn Write) -> Result<()> {
+        self.write_lines("documents", &output.documents, w)
+    }
+
+    fn outline(&self, output: &NotesOutline, w: &mut dyn Write) -> Result<()> {
+        write_line(output, self.fields.as_ref(), w)
+    }
+
+    fn actions(&self, output: &ActionsOutput, w: &mut dyn Write) -> Result<()> {
+        self.write_lines("items", &output.items, w)
+    }
+}
+
+impl NdjsonRenderer {
+    fn write_lines<T: Serialize>(
+        &self,
+        collection: &str,
+        items: &[T],
+        w: &mut dyn Write,
+    ) -> Result<()> {
+        let fields = self.fields.as_ref().map(|f| f.descend(collection));
+        for item in items {
+            write_line(item, fields, w)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_line<T: Serialize>(
+    value: &T,
+    fields: Option<&Projection>,
+    w: &mut dyn Write,
+) -> Result<()> {
+    match fields {
+        Some(fields) => {
+            serde_json::to_writer(&mut *w, &fields.apply(serde_json::to_value(value)?))?
+        }
+        None => serde_json::to_writer(&mut *w, value)?,
+    }
+    writeln!(w)?;
+    Ok(())
+}
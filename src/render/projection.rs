@@ -0,0 +1,56 @@
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// A set of dotted field paths (`results.id,results.title`) to keep in JSON
+/// output. Paths pass through arrays, so `results.id` keeps the `id` of every
+/// search result.
+#[derive(Debug, Clone, Default)]
+pub struct Projection {
+    children: BTreeMap<String, Projection>,
+}
+
+impl Projection {
+    pub fn parse(fields: &[String]) -> Self {
+        let mut root = Projection::default();
+        for field in fields {
+            let mut node = &mut root;
+            for part in field.trim().split('.').filter(|p| !p.is_empty()) {
+                node = node.children.entry(part.to_string()).or_default();
+            }
+        }
+        root
+    }
+
+    /// The projection to apply to each item of the `field` collection, for
+    /// output that is written one item at a time. Paths that do not start with
+    /// the collection name are treated as relative to the item.
+    pub fn descend(&self, field: &str) -> &Projection {
+        self.children.get(field).unwrap_or(self)
+    }
+
+    /// A projection that selects nothing below it keeps the whole value.
+    fn keeps_all(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    pub fn apply(&self, value: Value) -> Value {
+        if self.keeps_all() {
+            return value;
+        }
+
+        match value {
+            Value::Object(object) => {
+                let mut projected = Map::new();
+                // Keep the output's own field order rather than the order given
+                for (key, v) in object {
+                    if let Some(child) = self.children.get(&key) {
+                        projected.insert(key, child.apply(v));
+                    }
+                }
+                Value::Object(projected)
+            }
+            Value::Array(items) => Value::Array(items.into_iter().map(|v| self.apply(v)).collect()),
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use super::Renderer;
+use crate::error::Result;
+use crate::models::{CompactSegment, TranscriptOutput};
+use std::io::Write;
+
+/// Longest time a single cue stays on screen
+const MAX_CUE_SECONDS: i64 = 7;
+
+pub struct SrtRenderer;
+
+impl Renderer for SrtRenderer {
+    fn name(&self) -> &'static str {
+        "srt"
+    }
+
+    fn transcript(&self, output: &TranscriptOutput, w: &mut dyn Write) -> Result<()> {
+        for (i, cue) in cues(&output.segments).iter().enumerate() {
+            if i > 0 {
+                writeln!(w)?;
+            }
+            writeln!(
+                w,
+                "{}\n{} --> {}\n{}: {}",
+                i + 1,
+                format_time(cue.start, ','),
+                format_time(cue.end, ','),
+                cue.speaker,
+                cue.text
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct VttRenderer;
+
+impl Renderer for VttRenderer {
+    fn name(&self) -> &'static str {
+        "vtt"
+    }
+
+    fn transcript(&self, output: &TranscriptOutput, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "WEBVTT")?;
+        for cue in cues(&output.segments) {
+            writeln!(
+                w,
+                "\n{} --> {}\n<v {}>{}",
+                format_time(cue.start, '.'),
+                format_time(cue.end, '.'),
+                escape_vtt(cue.speaker),
+                escape_vtt(&cue.text)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+struct Cue<'a> {
+    start: i64,
+    end: i64,
+    speaker: &'a str,
+    text: String,
+}
+
+fn cues(segments: &[CompactSegment]) -> Vec<Cue<'_>> {
+    segments
+        .iter()
+        .enumerate()
+        .filter_map(|(i, segment)| {
+            let text = single_block(&segment.t);
+            if text.is_empty() {
+                return None;
+            }
+
+            // A cue runs until the next segment starts (or the merged turn
+            // ends), but never longer than MAX_CUE_SECONDS
+            let start = segment.ts;
+            let limit = start + MAX_CUE_SECONDS;
+            let end = match segments.get(i + 1) {
+                Some(next) => next.ts.min(limit),
+                None => segment.te.unwrap_or(limit).min(limit),
+            };
+
+            Some(Cue {
+                start,
+                end: end.max(start + 1),
+                speaker: &segment.s,
+                text,
+            })
+        })
+        .collect()
+}
+
+/// Blank lines end a cue in both formats, so collapse them.
+fn single_block(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_time(seconds: i64, millis_separator: char) -> String {
+    let seconds = seconds.max(0);
+    format!(
+        "{:02}:{:02}:{:02}{}000",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60,
+        millis_separator
+    )
+}
+
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
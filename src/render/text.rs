@@ -0,0 +1,262 @@
+use super::{format_date, format_setting, format_timestamp, yes_no, Renderer};
+use crate::error::Result;
+use crate::models::{
+    ActionsOutput, ConfigOutput, DocumentsOutput, ExportOutput, MeetingDetails, NotesOutline,
+    OutlineHeading, SearchOutput, TranscriptOutput, TranscriptStats,
+};
+use std::io::Write;
+
+pub struct TextRenderer;
+
+impl Renderer for TextRenderer {
+    fn name(&self) -> &'static str {
+        "text"
+    }
+
+    fn search(&self, output: &SearchOutput, w: &mut dyn Write) -> Result<()> {
+        let rows: Vec<Vec<String>> = output
+            .results
+            .iter()
+            .map(|r| {
+                vec![
+                    r.id.clone(),
+                    format_date(&r.date),
+                    r.title.clone(),
+                    r.participants.join(", "),
+                    yes_no(r.has_transcript).to_string(),
+                    yes_no(r.has_notes).to_string(),
+                ]
+            })
+            .collect();
+
+        writeln!(
+            w,
+            "{} result(s) for \"{}\"\n\n{}",
+            output.total_matches,
+            output.query,
+            table(
+                &["ID", "DATE", "TITLE", "PARTICIPANTS", "TRANSCRIPT", "NOTES"],
+                &rows
+            )
+        )?;
+        Ok(())
+    }
+
+    fn details(&self, output: &MeetingDetails, w: &mut dyn Write) -> Result<()> {
+        let participants: Vec<String> = output
+            .participants
+            .iter()
+            .map(|p| match &p.email {
+                Some(email) => format!("{} <{}>", p.name, email),
+                None => p.name.clone(),
+            })
+            .collect();
+        let duration = output
+            .duration_minutes
+            .map(|m| format!("{} min", m))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        writeln!(
+            w,
+            "{}\n\nID:           {}\nDate:         {}\nDuration:     {}\nType:         {}\nParticipants: {}\nTranscript:   {}\nNotes:        {}\nUpdated:      {}",
+            output.title,
+            output.id,
+            format_date(&output.date),
+            duration,
+            output.meeting_type,
+            participants.join(", "),
+            yes_no(output.has_transcript),
+            yes_no(output.has_notes),
+            format_date(&output.updated_at),
+        )?;
+        Ok(())
+    }
+
+    fn transcript(&self, output: &TranscriptOutput, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "{}", output.title)?;
+        for segment in &output.segments {
+            writeln!(
+                w,
+                "\n{} [{}]: {}",
+                segment.s,
+                format_timestamp(segment.ts),
+                segment.t
+            )?;
+        }
+        Ok(())
+    }
+
+    fn stats(&self, output: &TranscriptStats, w: &mut dyn Write) -> Result<()> {
+        let rows: Vec<Vec<String>> = output
+            .speakers
+            .iter()
+            .map(|s| {
+                vec![
+                    s.speaker.clone(),
+                    format!(
+                        "{} ({}%)",
+                        format_timestamp(s.talk_time_seconds),
+                        s.talk_time_percent
+                    ),
+                    s.word_count.to_string(),
+                    s.turn_count.to_string(),
+                    format_timestamp(s.longest_monologue_seconds),
+                    s.interruptions.to_string(),
+                ]
+            })
+            .collect();
+
+        writeln!(
+            w,
+            "{}\n\n{}\n\nSilence: {} across {} gap(s)",
+            output.title,
+            table(
+                &[
+                    "SPEAKER",
+                    "TALK TIME",
+                    "WORDS",
+                    "TURNS",
+                    "LONGEST",
+                    "INTERRUPTIONS"
+                ],
+                &rows
+            ),
+            format_timestamp(output.total_silence_seconds),
+            output.silence_gaps.len()
+        )?;
+        Ok(())
+    }
+
+    fn documents(&self, output: &DocumentsOutput, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "{}", output.title)?;
+        for doc in &output.documents {
+            writeln!(w, "\n== {} ==\n\n{}", doc.title, doc.content.trim_end())?;
+        }
+        Ok(())
+    }
+
+    fn outline(&self, output: &NotesOutline, w: &mut dyn Write) -> Result<()> {
+        writeln!(w, "{}\n", output.title)?;
+        write_headings(&output.headings, 0, w)
+    }
+
+    fn actions(&self, output: &ActionsOutput, w: &mut dyn Write) -> Result<()> {
+        let rows: Vec<Vec<String>> = output
+            .items
+            .iter()
+            .map(|item| {
+                vec![
+                    if item.completed { "[x]" } else { "[ ]" }.to_string(),
+                    format_date(&item.date),
+                    item.assignee.clone().unwrap_or_default(),
+                    item.text.clone(),
+                    item.meeting_title.clone(),
+                ]
+            })
+            .collect();
+
+        writeln!(
+            w,
+            "{} action item(s)\n\n{}",
+            output.total_items,
+            table(&["DONE", "DATE", "OWNER", "ITEM", "MEETING"], &rows)
+        )?;
+        Ok(())
+    }
+
+    fn config(&self, output: &ConfigOutput, w: &mut dyn Write) -> Result<()> {
+        let rows: Vec<Vec<String>> = output
+            .settings
+            .iter()
+            .map(|s| vec![s.key.clone(), format_setting(&s.value), s.source.clone()])
+            .collect();
+
+        let exists = if output.config_file_exists {
+            ""
+        } else {
+            " (not found)"
+        };
+        writeln!(w, "Config file: {}{}", output.config_file, exists)?;
+        if let Some(profile) = &output.profile {
+            writeln!(w, "Profile:     {}", profile)?;
+        }
+        writeln!(w, "\n{}", table(&["KEY", "VALUE", "SOURCE"], &rows))?;
+        Ok(())
+    }
+
+    fn export(&self, output: &ExportOutput, w: &mut dyn Write) -> Result<()> {
+        // Database exports have no file per meeting
+        let location = if output.files.iter().all(|f| f.path.is_none()) {
+            "MEETING"
+        } else {
+            "PATH"
+        };
+        let rows: Vec<Vec<String>> = output
+            .files
+            .iter()
+            .map(|f| {
+                vec![
+                    f.status.clone(),
+                    f.path.clone().unwrap_or_else(|| f.meeting_id.clone()),
+                ]
+            })
+            .collect();
+
+        writeln!(
+            w,
+            "Exported {} meeting(s) to {} ({} written, {} unchanged)\n\n{}",
+            output.total_meetings,
+            output.destination,
+            output.written,
+            output.unchanged,
+            table(&["STATUS", location], &rows)
+        )?;
+        if !output.removed_files.is_empty() {
+            writeln!(
+                w,
+                "\nRemoved {} file(s) of renamed meetings",
+                output.removed_files.len()
+            )?;
+        }
+        if !output.people_files.is_empty() {
+            writeln!(w, "\nUpdated {} person note(s)", output.people_files.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Headings indented two spaces per nesting level.
+fn write_headings(headings: &[OutlineHeading], depth: usize, w: &mut dyn Write) -> Result<()> {
+    for heading in headings {
+        writeln!(w, "{}{}", "  ".repeat(depth), heading.title)?;
+        write_headings(&heading.children, depth + 1, w)?;
+    }
+    Ok(())
+}
+
+/// Left-aligned columns separated by two spaces.
+fn table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(headers.to_vec())];
+    for row in rows {
+        lines.push(format_row(row.iter().map(String::as_str).collect()));
+    }
+    lines.join("\n")
+}
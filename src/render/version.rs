@@ -0,0 +1,95 @@
+use crate::error::{GranolaError, Result};
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// Versions of the JSON output layout.
+///
+/// Compatibility policy: a minor version only adds fields or commands, so a
+/// consumer written for 1.0 keeps working against 1.1 as long as it ignores
+/// unknown fields. Removing, renaming or retyping a field requires a new major
+/// version. Older layouts stay available through `--schema-version`, which
+/// strips everything added after the requested version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum SchemaVersion {
+    /// Original layout
+    #[value(name = "1.0")]
+    V1_0,
+    /// Adds transcript `window` and `te`, and the `stats` command
+    #[value(name = "1.1")]
+    V1_1,
+    /// Adds the `actions`, `config show` and `export` commands and
+    /// `documents --outline`
+    #[value(name = "1.2")]
+    V1_2,
+}
+
+impl SchemaVersion {
+    pub const CURRENT: SchemaVersion = SchemaVersion::V1_2;
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SchemaVersion::V1_0 => "1.0",
+            SchemaVersion::V1_1 => "1.1",
+            SchemaVersion::V1_2 => "1.2",
+        }
+    }
+
+    /// Rewrite the current output of `command` into this version's layout.
+    pub fn downgrade(self, command: &str, mut value: Value) -> Result<Value> {
+        if let Some((_, added)) = COMMANDS_ADDED
+            .iter()
+            .find(|(c, added)| *c == command && *added > self)
+        {
+            return Err(GranolaError::InvalidArguments(format!(
+                "The {} command requires --schema-version {} or later",
+                command,
+                added.as_str()
+            )));
+        }
+
+        for (c, path, added) in FIELDS_ADDED {
+            if *c == command && *added > self {
+                let parts: Vec<&str> = path.split('.').collect();
+                remove_path(&mut value, &parts);
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// Commands introduced after 1.0
+const COMMANDS_ADDED: &[(&str, SchemaVersion)] = &[
+    ("stats", SchemaVersion::V1_1),
+    ("actions", SchemaVersion::V1_2),
+    ("outline", SchemaVersion::V1_2),
+    ("config", SchemaVersion::V1_2),
+    ("export", SchemaVersion::V1_2),
+];
+
+/// Fields introduced after 1.0, as dotted paths that pass through arrays
+const FIELDS_ADDED: &[(&str, &str, SchemaVersion)] = &[
+    ("transcript", "window", SchemaVersion::V1_1),
+    ("transcript", "segments.te", SchemaVersion::V1_1),
+];
+
+fn remove_path(value: &mut Value, path: &[&str]) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                remove_path(item, path);
+            }
+        }
+        Value::Object(object) => match path {
+            [] => {}
+            [last] => {
+                object.shift_remove(*last);
+            }
+            [first, rest @ ..] => {
+                if let Some(child) = object.get_mut(*first) {
+                    remove_path(child, rest);
+                }
+            }
+        },
+        _ => {}
+    }
+}
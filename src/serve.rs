@@ -0,0 +1,196 @@
+use crate::cache::LiveCache;
+use crate::commands;
+use crate::commands::documents::NotesFormat;
+use crate::commands::search::DEFAULT_LIMIT;
+use crate::commands::transcript::{TimeOffset, TranscriptOptions};
+use crate::error::{GranolaError, Result};
+use crate::models::{Cache, ErrorDetail, ErrorOutput};
+use crate::speakers::SpeakerMapping;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Serve meeting data over HTTP until the process is stopped.
+pub fn serve(cache_path: PathBuf, addr: &str) -> Result<()> {
+    let mut cache = LiveCache::load(cache_path)?;
+
+    let server = Server::http(addr).map_err(|e| {
+        GranolaError::IoError(io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            format!("Could not listen on {}: {}", addr, e),
+        ))
+    })?;
+    eprintln!(
+        "Listening on http://{}",
+        server
+            .server_addr()
+            .to_ip()
+            .map(|a| a.to_string())
+            .unwrap_or_else(|| addr.to_string())
+    );
+
+    for request in server.incoming_requests() {
+        cache.refresh();
+        let (status, body) = handle(&request, cache.cache());
+        let response = Response::from_data(body)
+            .with_status_code(status)
+            .with_header(
+                "Content-Type: application/json"
+                    .parse::<Header>()
+                    .expect("static header is valid"),
+            );
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn handle(request: &Request, cache: &Cache) -> (u16, Vec<u8>) {
+    if *request.method() != Method::Get {
+        return error_response(405, 5, "method_not_allowed", "Only GET is supported");
+    }
+
+    let (path, query) = match request.url().split_once('?') {
+        Some((path, query)) => (path, parse_query(query)),
+        None => (request.url(), HashMap::new()),
+    };
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(percent_decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let result = match segments.as_slice() {
+        ["search"] => search(&query, cache),
+        ["meetings", id] => json(commands::get_meeting_details(id, cache)),
+        ["meetings", id, "transcript"] => transcript(id, &query, cache),
+        ["meetings", id, "documents"] => documents(id, &query, cache),
+        _ => return error_response(404, 4, "not_found", &format!("No route for {}", path)),
+    };
+
+    match result {
+        Ok(body) => (200, body),
+        Err(e) => {
+            let status = match e.exit_code() {
+                4 => 404,
+                5 => 400,
+                _ => 500,
+            };
+            (status, to_body(&e.to_json()))
+        }
+    }
+}
+
+fn search(query: &HashMap<String, String>, cache: &Cache) -> Result<Vec<u8>> {
+    let q = query.get("q").map(String::as_str).unwrap_or("");
+    let limit = match query.get("limit") {
+        Some(limit) => limit
+            .parse()
+            .map_err(|_| GranolaError::InvalidArguments(format!("Invalid limit '{}'", limit)))?,
+        None => DEFAULT_LIMIT,
+    };
+    json(commands::search_meetings(q, limit, cache))
+}
+
+fn transcript(id: &str, query: &HashMap<String, String>, cache: &Cache) -> Result<Vec<u8>> {
+    let options = TranscriptOptions {
+        from: query
+            .get("from")
+            .map(|s| TimeOffset::parse(s))
+            .transpose()?,
+        to: query.get("to").map(|s| TimeOffset::parse(s)).transpose()?,
+        merge_turns: flag(query, "merge_turns"),
+        speaker: query.get("speaker").cloned(),
+        speaker_mapping: flag(query, "map_speakers")
+            .then(SpeakerMapping::from_participants)
+            .unwrap_or_default(),
+    };
+    json(commands::get_transcript(id, &options, cache))
+}
+
+fn documents(id: &str, query: &HashMap<String, String>, cache: &Cache) -> Result<Vec<u8>> {
+    if flag(query, "outline") {
+        return json(commands::get_notes_outline(id, cache));
+    }
+    if let Some(section) = query.get("section") {
+        return json(commands::get_notes_section(id, section, cache));
+    }
+
+    let notes_format = match query.get("notes_format") {
+        Some(format) => NotesFormat::from_str(format, true).map_err(|_| {
+            GranolaError::InvalidArguments(format!("Invalid notes_format '{}'", format))
+        })?,
+        None => NotesFormat::Plain,
+    };
+    json(commands::get_documents(id, notes_format, cache))
+}
+
+fn json<T: Serialize>(output: Result<T>) -> Result<Vec<u8>> {
+    Ok(to_body(&output?))
+}
+
+fn to_body<T: Serialize>(value: &T) -> Vec<u8> {
+    serde_json::to_vec(value).expect("output types always serialize")
+}
+
+/// Errors that don't come from a command, with the exit code the CLI would
+/// use for the same kind of failure.
+fn error_response(status: u16, code: i32, error_type: &str, message: &str) -> (u16, Vec<u8>) {
+    let output = ErrorOutput {
+        error: ErrorDetail {
+            code,
+            error_type: error_type.to_string(),
+            message: message.to_string(),
+            suggestion: None,
+        },
+    };
+    (status, to_body(&output))
+}
+
+/// `?merge_turns`, `?merge_turns=true` and `?merge_turns=1` all switch a flag on.
+fn flag(query: &HashMap<String, String>, name: &str) -> bool {
+    query
+        .get(name)
+        .is_some_and(|v| v.is_empty() || v == "true" || v == "1")
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes and `+` as space, leaving malformed escapes as-is.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
@@ -0,0 +1,110 @@
+use crate::error::{GranolaError, Result};
+use crate::models::CacheDocument;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Granola records the local user's microphone and the call audio as two
+/// separate transcript sources.
+const MICROPHONE_SOURCE: &str = "microphone";
+const SYSTEM_SOURCE: &str = "system";
+
+/// How raw transcript sources (`microphone`, `system`, ...) are turned into
+/// speaker labels.
+#[derive(Debug, Default, Clone)]
+pub struct SpeakerMapping {
+    /// Guess names from the meeting's creator and attendees
+    pub from_participants: bool,
+    /// Explicit source -> name mapping, takes priority over guessed names
+    pub names: HashMap<String, String>,
+}
+
+impl SpeakerMapping {
+    /// Name speakers after the meeting's participants, with no explicit map.
+    pub fn from_participants() -> Self {
+        SpeakerMapping {
+            from_participants: true,
+            ..Default::default()
+        }
+    }
+
+    /// Load a mapping file: a JSON object of source -> name, e.g.
+    /// `{"microphone": "Alice", "system": "Customer"}`.
+    pub fn load_names(path: &Path) -> Result<HashMap<String, String>> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            GranolaError::InvalidArguments(format!(
+                "Cannot read speaker map {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            GranolaError::InvalidArguments(format!(
+                "Speaker map {} must be a JSON object of source to name: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Resolve the labels to use for one meeting.
+    pub fn labels_for(&self, doc: &CacheDocument) -> SpeakerLabels {
+        let mut labels = HashMap::new();
+
+        if self.from_participants {
+            labels.extend(participant_labels(doc));
+        }
+        labels.extend(self.names.clone());
+
+        SpeakerLabels(labels)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SpeakerLabels(HashMap<String, String>);
+
+impl SpeakerLabels {
+    pub fn label(&self, source: &str) -> String {
+        self.0
+            .get(source)
+            .cloned()
+            .unwrap_or_else(|| source.to_string())
+    }
+
+    /// Whether a user-supplied speaker `name` refers to `label`, given either
+    /// as the label itself or as the raw source it was mapped from.
+    pub fn matches(&self, label: &str, name: &str) -> bool {
+        label.eq_ignore_ascii_case(name) || self.label(name).eq_ignore_ascii_case(label)
+    }
+}
+
+fn participant_labels(doc: &CacheDocument) -> HashMap<String, String> {
+    let mut labels = HashMap::new();
+
+    let Some(people) = &doc.people else {
+        return labels;
+    };
+
+    // The microphone is the person who recorded the meeting
+    if let Some(creator) = &people.creator {
+        labels.insert(MICROPHONE_SOURCE.to_string(), creator.name.clone());
+    }
+
+    // Call audio can only be attributed when there is a single other attendee
+    let others: Vec<_> = people
+        .attendees
+        .iter()
+        .flatten()
+        .filter(|a| match &people.creator {
+            Some(creator) => {
+                a.name != creator.name && (a.email.is_none() || a.email != creator.email)
+            }
+            None => true,
+        })
+        .collect();
+    if let [other] = others.as_slice() {
+        labels.insert(SYSTEM_SOURCE.to_string(), other.name.clone());
+    }
+
+    labels
+}
@@ -0,0 +1,556 @@
+use crate::commands;
+use crate::commands::documents::NotesFormat;
+use crate::commands::transcript::{TimeOffset, TranscriptOptions};
+use crate::error::{GranolaError, Result};
+use crate::models::{Cache, DocumentsOutput, MeetingDetails, SearchResult, TranscriptOutput};
+use crate::render::markdown::MarkdownRenderer;
+use crate::render::{format_date, format_timestamp, Renderer};
+use crate::speakers::SpeakerMapping;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io::{IsTerminal, Write};
+
+/// Colours cycled through by speaker, in order of first appearance
+const SPEAKER_COLORS: &[Color] = &[
+    Color::Cyan,
+    Color::Yellow,
+    Color::Green,
+    Color::Magenta,
+    Color::Blue,
+    Color::Red,
+];
+
+const PAGE: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    List,
+    Content,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Transcript,
+    Notes,
+}
+
+/// What typed characters currently go to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Input {
+    None,
+    Search,
+    Jump,
+}
+
+/// The meeting shown on the right, loaded when the selection changes.
+struct Meeting {
+    details: MeetingDetails,
+    transcript: Option<TranscriptOutput>,
+    documents: DocumentsOutput,
+}
+
+struct App<'a> {
+    cache: &'a Cache,
+    query: String,
+    results: Vec<SearchResult>,
+    list: ListState,
+    meeting: Option<Meeting>,
+    focus: Focus,
+    view: View,
+    input: Input,
+    jump: String,
+    /// First transcript segment shown
+    transcript_scroll: usize,
+    /// First notes line shown
+    notes_scroll: usize,
+    status: String,
+    quit: bool,
+}
+
+/// Browse meetings interactively until the user quits.
+pub fn run(cache: &Cache) -> Result<()> {
+    if !std::io::stdout().is_terminal() {
+        return Err(GranolaError::InvalidArguments(
+            "granola tui needs an interactive terminal".to_string(),
+        ));
+    }
+
+    let mut app = App::new(cache)?;
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl<'a> App<'a> {
+    fn new(cache: &'a Cache) -> Result<Self> {
+        let mut app = App {
+            cache,
+            query: String::new(),
+            results: Vec::new(),
+            list: ListState::default(),
+            meeting: None,
+            focus: Focus::List,
+            view: View::Notes,
+            input: Input::None,
+            jump: String::new(),
+            transcript_scroll: 0,
+            notes_scroll: 0,
+            status:
+                "/ search  tab focus  t/n transcript/notes  : jump  y copy ID  e export  q quit"
+                    .to_string(),
+            quit: false,
+        };
+        app.search()?;
+        Ok(app)
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn search(&mut self) -> Result<()> {
+        self.results = commands::search_meetings(&self.query, usize::MAX, self.cache)?.results;
+        self.list.select(if self.results.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+        self.load_selected();
+        Ok(())
+    }
+
+    /// Load the selected meeting, showing any error in the status line
+    /// rather than leaving the TUI.
+    fn load_selected(&mut self) {
+        self.transcript_scroll = 0;
+        self.notes_scroll = 0;
+        let Some(id) = self.selected().map(|r| r.id.clone()) else {
+            self.meeting = None;
+            return;
+        };
+        match self.load_meeting(&id) {
+            Ok(meeting) => self.meeting = Some(meeting),
+            Err(e) => {
+                self.meeting = None;
+                self.status = format!("Could not load {}: {}", id, e);
+            }
+        }
+    }
+
+    fn load_meeting(&self, id: &str) -> Result<Meeting> {
+        let options = TranscriptOptions {
+            speaker_mapping: SpeakerMapping::from_participants(),
+            ..Default::default()
+        };
+        Ok(Meeting {
+            details: commands::get_meeting_details(id, self.cache)?,
+            // Meetings without a transcript still have details and notes
+            transcript: commands::get_transcript(id, &options, self.cache).ok(),
+            documents: commands::get_documents(id, NotesFormat::Markdown, self.cache)?,
+        })
+    }
+
+    fn selected(&self) -> Option<&SearchResult> {
+        self.list.selected().and_then(|i| self.results.get(i))
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return Ok(());
+        }
+
+        match self.input {
+            Input::Search => self.handle_search_key(key),
+            Input::Jump => {
+                self.handle_jump_key(key);
+                Ok(())
+            }
+            Input::None => {
+                self.handle_command_key(key);
+                Ok(())
+            }
+        }
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => self.input = Input::None,
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.search()?;
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.search()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_jump_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.input = Input::None,
+            KeyCode::Backspace => {
+                self.jump.pop();
+            }
+            KeyCode::Char(c) => self.jump.push(c),
+            KeyCode::Enter => {
+                self.input = Input::None;
+                self.jump_to(&self.jump.clone());
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_command_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('/') => {
+                self.input = Input::Search;
+                self.focus = Focus::List;
+            }
+            KeyCode::Char(':') => {
+                self.input = Input::Jump;
+                self.jump.clear();
+                self.view = View::Transcript;
+                self.focus = Focus::Content;
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.focus = match self.focus {
+                    Focus::List => Focus::Content,
+                    Focus::Content => Focus::List,
+                }
+            }
+            KeyCode::Char('t') => self.view = View::Transcript,
+            KeyCode::Char('n') => self.view = View::Notes,
+            KeyCode::Char('y') => self.copy_id(),
+            KeyCode::Char('e') => self.export(),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll(-1),
+            KeyCode::PageDown => self.scroll(PAGE as isize),
+            KeyCode::PageUp => self.scroll(-(PAGE as isize)),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.scroll(isize::MAX),
+            _ => {}
+        }
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        match self.focus {
+            Focus::List => {
+                let Some(current) = self.list.selected() else {
+                    return;
+                };
+                let next = offset(current, delta, self.results.len());
+                if next != current {
+                    self.list.select(Some(next));
+                    self.load_selected();
+                }
+            }
+            Focus::Content => match self.view {
+                View::Transcript => {
+                    let len = self.transcript().map_or(0, |t| t.segments.len());
+                    self.transcript_scroll = offset(self.transcript_scroll, delta, len);
+                }
+                View::Notes => {
+                    let len = self.notes_text().lines().count();
+                    self.notes_scroll = offset(self.notes_scroll, delta, len);
+                }
+            },
+        }
+    }
+
+    /// Scroll the transcript to the first segment at or after a time.
+    fn jump_to(&mut self, input: &str) {
+        let Some(transcript) = self.transcript() else {
+            self.status = "This meeting has no transcript".to_string();
+            return;
+        };
+        match TimeOffset::parse(input) {
+            Ok(offset) => {
+                let end = transcript.segments.last().map_or(0, |s| s.ts);
+                let target = offset.resolve(end);
+                let index = transcript
+                    .segments
+                    .iter()
+                    .position(|s| s.ts >= target)
+                    .unwrap_or(transcript.segments.len().saturating_sub(1));
+                self.transcript_scroll = index;
+                self.status = format!("Jumped to {}", format_timestamp(target));
+            }
+            Err(_) => self.status = format!("Invalid time '{}', use mm:ss", input),
+        }
+    }
+
+    /// Copy the selected meeting's ID with an OSC 52 escape, which most
+    /// terminals (including over SSH) forward to the system clipboard.
+    fn copy_id(&mut self) {
+        let Some(id) = self.selected().map(|r| r.id.clone()) else {
+            return;
+        };
+        let mut stdout = std::io::stdout();
+        let copied =
+            write!(stdout, "\x1b]52;c;{}\x07", base64(id.as_bytes())).and_then(|()| stdout.flush());
+        self.status = match copied {
+            Ok(()) => format!("Copied {}", id),
+            Err(e) => format!("Could not copy {}: {}", id, e),
+        };
+    }
+
+    /// Write the current meeting to `<id>.md` in the working directory.
+    fn export(&mut self) {
+        let Some(meeting) = &self.meeting else {
+            return;
+        };
+
+        let path = format!("{}.md", meeting.details.id);
+        self.status = match write_markdown(meeting, &path) {
+            Ok(()) => format!("Exported to {}", path),
+            Err(e) => format!("Could not export to {}: {}", path, e),
+        };
+    }
+
+    fn transcript(&self) -> Option<&TranscriptOutput> {
+        self.meeting.as_ref().and_then(|m| m.transcript.as_ref())
+    }
+
+    fn notes_text(&self) -> String {
+        self.meeting
+            .as_ref()
+            .map(|m| {
+                m.documents
+                    .documents
+                    .iter()
+                    .map(|d| format!("## {}\n\n{}", d.title, d.content.trim_end()))
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+            })
+            .unwrap_or_default()
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(main);
+        let [details, content] =
+            Layout::vertical([Constraint::Length(8), Constraint::Min(0)]).areas(right);
+
+        self.draw_list(frame, left);
+        self.draw_details(frame, details);
+        self.draw_content(frame, content);
+
+        let status_line = match self.input {
+            Input::Search => format!("Search: {}_", self.query),
+            Input::Jump => format!("Jump to (mm:ss, -mm:ss from end): {}_", self.jump),
+            Input::None => self.status.clone(),
+        };
+        frame.render_widget(Paragraph::new(status_line).reversed(), status);
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .map(|r| {
+                ListItem::new(vec![
+                    Line::from(r.title.clone()).bold(),
+                    Line::from(format_date(&r.date)).dim(),
+                ])
+            })
+            .collect();
+
+        let title = if self.query.is_empty() {
+            format!(" Meetings ({}) ", self.results.len())
+        } else {
+            format!(
+                " Meetings matching \"{}\" ({}) ",
+                self.query,
+                self.results.len()
+            )
+        };
+        let list = List::new(items)
+            .block(self.block(title, self.focus == Focus::List))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.list);
+    }
+
+    fn draw_details(&self, frame: &mut Frame, area: Rect) {
+        let lines = match &self.meeting {
+            Some(m) => {
+                let d = &m.details;
+                let participants: Vec<&str> =
+                    d.participants.iter().map(|p| p.name.as_str()).collect();
+                vec![
+                    Line::from(d.title.clone()).bold(),
+                    Line::from(vec!["ID: ".dim(), Span::raw(d.id.clone())]),
+                    Line::from(vec!["Date: ".dim(), Span::raw(format_date(&d.date))]),
+                    Line::from(vec![
+                        "Duration: ".dim(),
+                        Span::raw(
+                            d.duration_minutes
+                                .map(|m| format!("{} min", m))
+                                .unwrap_or_else(|| "unknown".to_string()),
+                        ),
+                    ]),
+                    Line::from(vec![
+                        "Participants: ".dim(),
+                        Span::raw(participants.join(", ")),
+                    ]),
+                ]
+            }
+            None => vec![Line::from("No meeting selected").dim()],
+        };
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered().title(" Details ")),
+            area,
+        );
+    }
+
+    fn draw_content(&self, frame: &mut Frame, area: Rect) {
+        let [tabs, body] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+        let selected = match self.view {
+            View::Transcript => 0,
+            View::Notes => 1,
+        };
+        frame.render_widget(
+            Tabs::new(["Transcript (t)", "Notes (n)"])
+                .select(selected)
+                .highlight_style(Style::default().bold().underlined()),
+            tabs,
+        );
+
+        let focused = self.focus == Focus::Content;
+        let paragraph = match self.view {
+            View::Transcript => match self.transcript() {
+                Some(transcript) => {
+                    Paragraph::new(transcript_lines(transcript, self.transcript_scroll)).block(
+                        self.block(
+                            format!(
+                                " Transcript {}/{} ",
+                                (self.transcript_scroll + 1).min(transcript.segments.len()),
+                                transcript.segments.len()
+                            ),
+                            focused,
+                        ),
+                    )
+                }
+                None => Paragraph::new("No transcript for this meeting".dim())
+                    .block(self.block(" Transcript ".to_string(), focused)),
+            },
+            View::Notes => {
+                let text = self.notes_text();
+                let lines: Vec<Line> = text
+                    .lines()
+                    .skip(self.notes_scroll)
+                    .map(|line| {
+                        if line.starts_with('#') {
+                            Line::from(line.to_string()).bold()
+                        } else {
+                            Line::from(line.to_string())
+                        }
+                    })
+                    .collect();
+                Paragraph::new(lines).block(self.block(" Notes ".to_string(), focused))
+            }
+        };
+        frame.render_widget(paragraph.wrap(Wrap { trim: false }), body);
+    }
+
+    fn block(&self, title: String, focused: bool) -> Block<'static> {
+        let block = Block::bordered().title(title);
+        if focused {
+            block.border_style(Style::default().fg(Color::Cyan))
+        } else {
+            block
+        }
+    }
+}
+
+fn transcript_lines(transcript: &TranscriptOutput, scroll: usize) -> Vec<Line<'static>> {
+    transcript
+        .segments
+        .iter()
+        .skip(scroll)
+        .map(|segment| {
+            let index = transcript
+                .speakers
+                .iter()
+                .position(|s| *s == segment.s)
+                .unwrap_or(0);
+            let color = SPEAKER_COLORS[index % SPEAKER_COLORS.len()];
+            Line::from(vec![
+                Span::raw(format!("[{}] ", format_timestamp(segment.ts))).dim(),
+                Span::styled(
+                    format!("{}: ", segment.s),
+                    Style::default().fg(color).bold(),
+                ),
+                Span::raw(segment.t.clone()),
+            ])
+        })
+        .collect()
+}
+
+/// Write a meeting's details, notes and transcript as Markdown.
+fn write_markdown(meeting: &Meeting, path: &str) -> Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    let renderer = MarkdownRenderer;
+    renderer.details(&meeting.details, &mut file)?;
+    writeln!(file)?;
+    renderer.documents(&meeting.documents, &mut file)?;
+    if let Some(transcript) = &meeting.transcript {
+        writeln!(file)?;
+        renderer.transcript(transcript, &mut file)?;
+    }
+    Ok(())
+}
+
+/// Move `current` by `delta`, clamped to `0..len`.
+fn offset(current: usize, delta: isize, len: usize) -> usize {
+    let max = len.saturating_sub(1);
+    if delta < 0 {
+        current.saturating_sub(delta.unsigned_abs())
+    } else {
+        current.saturating_add(delta as usize).min(max)
+    }
+}
+
+fn base64(input: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,203 @@
+use crate::cache;
+use crate::error::Result;
+use crate::models::{Cache, CacheDocument, WatchEvent, WatchEventKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Copy)]
+pub struct WatchOptions {
+    /// How often to check the cache file for changes
+    pub interval: Duration,
+    /// How long the file must stay unchanged before it is read
+    pub debounce: Duration,
+}
+
+/// What `watch` remembers about a meeting between loads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MeetingState {
+    pub updated_at: String,
+    pub transcript_segments: usize,
+    /// Hash of the notes in all their formats
+    pub notes_hash: u64,
+}
+
+/// The state of every meeting in one load of the cache.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub meetings: HashMap<String, MeetingState>,
+}
+
+impl Snapshot {
+    pub fn of(cache: &Cache) -> Snapshot {
+        let meetings = cache
+            .documents
+            .values()
+            .map(|doc| {
+                let state = MeetingState {
+                    updated_at: doc.updated_at.clone(),
+                    transcript_segments: cache.transcripts.get(&doc.id).map_or(0, Vec::len),
+                    notes_hash: notes_hash(doc),
+                };
+                (doc.id.clone(), state)
+            })
+            .collect();
+        Snapshot { meetings }
+    }
+
+    /// Events that turn this snapshot into `next`, oldest change first.
+    /// Deleted meetings are not reported.
+    pub fn changes(&self, next: &Snapshot, cache: &Cache) -> Vec<WatchEvent> {
+        let mut ids: Vec<&String> = next.meetings.keys().collect();
+        ids.sort_by_key(|id| (&next.meetings[*id].updated_at, *id));
+
+        let mut events = Vec::new();
+        for id in ids {
+            let new = &next.meetings[id];
+            let title = cache
+                .documents
+                .get(id)
+                .map(|doc| doc.title.clone())
+                .unwrap_or_default();
+            let event = |event, previous_transcript_segments| WatchEvent {
+                event,
+                meeting_id: id.clone(),
+                title: title.clone(),
+                updated_at: new.updated_at.clone(),
+                transcript_segments: new.transcript_segments,
+                previous_transcript_segments,
+            };
+
+            let Some(old) = self.meetings.get(id) else {
+                events.push(event(WatchEventKind::MeetingCreated, None));
+                continue;
+            };
+            if new.updated_at != old.updated_at {
+                events.push(event(WatchEventKind::MeetingUpdated, None));
+            }
+            if new.transcript_segments > old.transcript_segments {
+                events.push(event(
+                    WatchEventKind::TranscriptExtended,
+                    Some(old.transcript_segments),
+                ));
+            }
+            if new.notes_hash != old.notes_hash {
+                events.push(event(WatchEventKind::NotesChanged, None));
+            }
+        }
+        events
+    }
+}
+
+/// Poll the cache file and call `on_change` with the events from each new
+/// version of it, until `on_change` fails.
+pub fn watch(
+    cache_path: &Path,
+    options: WatchOptions,
+    mut on_change: impl FnMut(&[WatchEvent], &Cache) -> Result<()>,
+) -> Result<()> {
+    let mut watcher = CacheWatcher::new(cache_path, options);
+    let mut snapshot = Snapshot::of(&watcher.load()?);
+    eprintln!("Watching {}", cache_path.display());
+
+    loop {
+        thread::sleep(options.interval);
+        let Some(cache) = watcher.poll() else {
+            continue;
+        };
+        let next = Snapshot::of(&cache);
+        let events = snapshot.changes(&next, &cache);
+        if !events.is_empty() {
+            on_change(&events, &cache)?;
+        }
+        snapshot = next;
+    }
+}
+
+/// Reloads the cache once Granola has finished writing a new version of it.
+pub struct CacheWatcher<'a> {
+    path: &'a Path,
+    debounce: Duration,
+    stamp: FileStamp,
+}
+
+impl<'a> CacheWatcher<'a> {
+    pub fn new(path: &'a Path, options: WatchOptions) -> Self {
+        CacheWatcher {
+            path,
+            debounce: options.debounce,
+            stamp: None,
+        }
+    }
+
+    /// Load the current version, whether or not it changed.
+    pub fn load(&mut self) -> Result<Cache> {
+        self.stamp = file_stamp(self.path);
+        cache::load_cache(&self.path.to_path_buf())
+    }
+
+    /// The new version of the cache if the file changed since the last load.
+    pub fn poll(&mut self) -> Option<Cache> {
+        let current = file_stamp(self.path);
+        if current == self.stamp {
+            return None;
+        }
+
+        // Granola rewrites the whole file; wait for it to stop changing
+        let current = settle(self.path, current, self.debounce);
+        match cache::load_cache(&self.path.to_path_buf()) {
+            Ok(cache) => {
+                self.stamp = current;
+                Some(cache)
+            }
+            // Leave the stamp alone so the next poll tries again
+            Err(e) => {
+                eprintln!("Cache not readable yet, retrying: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Modification time and size, which change with every write.
+type FileStamp = Option<(SystemTime, u64)>;
+
+fn file_stamp(path: &Path) -> FileStamp {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Wait until the file has gone `debounce` without changing.
+fn settle(path: &Path, mut stamp: FileStamp, debounce: Duration) -> FileStamp {
+    loop {
+        thread::sleep(debounce);
+        let next = file_stamp(path);
+        if next == stamp {
+            return stamp;
+        }
+        stamp = next;
+    }
+}
+
+/// FNV-1a over every notes format, so edits show up whichever one Granola
+/// updated. Stable across builds, unlike `DefaultHasher`.
+fn notes_hash(doc: &CacheDocument) -> u64 {
+    let structured = doc.notes.as_ref().map(|n| n.to_string());
+    let parts = [
+        doc.notes_plain.as_deref(),
+        doc.notes_markdown.as_deref(),
+        structured.as_deref(),
+    ];
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for part in parts {
+        // Separate the parts so moving text between them changes the hash
+        for byte in part.unwrap_or("").bytes().chain([0xff]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
@@ -1,4 +1,67 @@
-use std::process::Command;
+// The original tests pass `&[...]` to `Command::args`
+#![allow(clippy::needless_borrows_for_generic_args)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Write the fixture state into a Granola-style cache file (the state is
+/// double-encoded as a JSON string under `cache`) and return its path.
+fn fixture_cache(name: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.json", name));
+    write_cache(&path, &fixture_state());
+    path
+}
+
+fn fixture_state() -> serde_json::Value {
+    serde_json::from_str(include_str!("fixtures/state.json")).unwrap()
+}
+
+fn write_cache(path: &std::path::Path, state: &serde_json::Value) {
+    let inner = serde_json::json!({ "state": state }).to_string();
+    let outer = serde_json::json!({ "cache": inner });
+    std::fs::write(path, outer.to_string()).unwrap();
+}
+
+/// The binary with no config file and none of the developer's `GRANOLA_*`
+/// variables, so their settings can't change the output.
+fn granola() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_granola-cli"));
+    isolate_env(&mut command);
+    command
+}
+
+fn isolate_env(command: &mut Command) -> &mut Command {
+    for (key, _) in std::env::vars_os() {
+        if key.to_string_lossy().contains("GRANOLA_") {
+            command.env_remove(key);
+        }
+    }
+    command.env(
+        "GRANOLA_CONFIG",
+        PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("no-config.toml"),
+    )
+}
+
+fn run_with_fixture(name: &str, args: &[&str]) -> Output {
+    let cache = fixture_cache(name);
+    granola()
+        .arg("--cache-path")
+        .arg(&cache)
+        .args(args)
+        .output()
+        .expect("Failed to execute command")
+}
+
+fn stdout_json(output: &Output) -> serde_json::Value {
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("stdout is not JSON")
+}
 
 #[test]
 fn test_help_command() {
@@ -25,6 +88,10 @@ fn test_workflow_command() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("Granola CLI Workflow Guide"));
     assert!(stdout.contains("Token Optimization Tips"));
+    // Schema outlines are generated from the output types
+    assert!(stdout.contains("\"has_transcript\": boolean,"));
+    assert!(!stdout.contains("{schemas}"));
+    assert!(stdout.contains("(current: 1.2)"));
 }
 
 #[test]
@@ -68,3 +135,1699 @@ fn test_json_errors_flag() {
     assert!(stdout.contains("\"code\""));
     assert!(stdout.contains("cache_not_found"));
 }
+
+#[test]
+fn test_transcript_time_window() {
+    let output = run_with_fixture(
+        "transcript_window",
+        &[
+            "transcript",
+            "meeting-roadmap",
+            "--from",
+            "0:10",
+            "--to",
+            "10:00",
+        ],
+    );
+    let json = stdout_json(&output);
+
+    assert_eq!(json["window"]["from_seconds"], 10);
+    assert_eq!(json["window"]["to_seconds"], 600);
+    let timestamps: Vec<i64> = json["segments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["ts"].as_i64().unwrap())
+        .collect();
+    assert_eq!(timestamps, vec![10, 75, 130, 600]);
+}
+
+#[test]
+fn test_transcript_window_from_end() {
+    let output = run_with_fixture(
+        "transcript_window_end",
+        &["transcript", "meeting-roadmap", "--from", "-5:00"],
+    );
+    let json = stdout_json(&output);
+
+    assert_eq!(json["window"]["from_seconds"], 600);
+    assert_eq!(json["window"]["to_seconds"], 900);
+    assert_eq!(json["total_segments"], 2);
+}
+
+#[test]
+fn test_transcript_invalid_time() {
+    let output = run_with_fixture(
+        "transcript_invalid_time",
+        &["transcript", "meeting-roadmap", "--from", "1:75"],
+    );
+
+    assert_eq!(output.status.code(), Some(5));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid time"));
+}
+
+#[test]
+fn test_transcript_merge_turns() {
+    let output = run_with_fixture(
+        "transcript_merge_turns",
+        &["transcript", "meeting-roadmap", "--merge-turns"],
+    );
+    let json = stdout_json(&output);
+
+    assert_eq!(json["total_segments"], 5);
+    let first = &json["segments"][0];
+    assert_eq!(first["s"], "microphone");
+    assert_eq!(first["t"], "Welcome everyone. Let's get started.");
+    assert_eq!(first["ts"], 0);
+    assert_eq!(first["te"], 4);
+}
+
+#[test]
+fn test_transcript_speaker_filter() {
+    let output = run_with_fixture(
+        "transcript_speaker",
+        &["transcript", "meeting-roadmap", "--speaker", "system"],
+    );
+    let json = stdout_json(&output);
+
+    assert_eq!(json["speakers"], serde_json::json!(["system"]));
+    assert_eq!(json["total_segments"], 3);
+    assert!(json["segments"][0].get("te").is_none());
+}
+
+#[test]
+fn test_stats_command() {
+    let output = run_with_fixture("stats", &["stats", "meeting-roadmap"]);
+    let json = stdout_json(&output);
+
+    assert_eq!(json["total_segments"], 7);
+    let speakers = json["speakers"].as_array().unwrap();
+    assert_eq!(speakers.len(), 2);
+    assert_eq!(speakers[0]["speaker"], "microphone");
+    assert_eq!(speakers[0]["turn_count"], 3);
+    assert_eq!(speakers[0]["word_count"], 13);
+    assert_eq!(speakers[1]["speaker"], "system");
+    assert_eq!(speakers[1]["turn_count"], 2);
+    // Monologues end at silence gaps, so never exceed talk time
+    assert_eq!(speakers[0]["longest_monologue_seconds"], 3);
+    assert_eq!(speakers[1]["longest_monologue_seconds"], 2);
+    assert_eq!(speakers[1]["talk_time_seconds"], 6);
+    assert_eq!(json["silence_gaps"].as_array().unwrap().len(), 4);
+}
+
+#[test]
+fn test_stats_interruptions() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("stats_interruptions.json");
+    let mut state = fixture_state();
+    // Bob cuts in a second into Alice's two-second "Let's get started."
+    state["transcripts"]["meeting-roadmap"][2]["timestamp"] = 5.into();
+    // Alice answers 2s after Bob's "Revenue grew last quarter." would end,
+    // which is ordinary turn-taking
+    state["transcripts"]["meeting-roadmap"][4]["timestamp"] = 79.into();
+    write_cache(&path, &state);
+
+    let output = granola()
+        .arg("--cache-path")
+        .arg(&path)
+        .args(["stats", "meeting-roadmap"])
+        .output()
+        .expect("Failed to execute command");
+    let json = stdout_json(&output);
+    let speakers = json["speakers"].as_array().unwrap();
+    assert_eq!(speakers[0]["interruptions"], 0);
+    assert_eq!(speakers[1]["interruptions"], 1);
+}
+
+#[test]
+fn test_transcript_speakers_in_order_of_appearance() {
+    let output = run_with_fixture(
+        "transcript_speaker_order",
+        &["transcript", "meeting-roadmap"],
+    );
+    let json = stdout_json(&output);
+
+    assert_eq!(
+        json["speakers"],
+        serde_json::json!(["microphone", "system"])
+    );
+}
+
+#[test]
+fn test_transcript_map_speakers_from_participants() {
+    let output = run_with_fixture(
+        "transcript_map_speakers",
+        &[
+            "transcript",
+            "meeting-roadmap",
+            "--map-speakers",
+            "--speaker",
+            "system",
+        ],
+    );
+    let json = stdout_json(&output);
+
+    assert_eq!(json["speakers"], serde_json::json!(["Bob Jones"]));
+    assert_eq!(json["segments"][0]["s"], "Bob Jones");
+}
+
+#[test]
+fn test_stats_speaker_map_file() {
+    let map = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("speaker_map.json");
+    std::fs::write(&map, r#"{"microphone": "Me", "system": "Them"}"#).unwrap();
+
+    let output = run_with_fixture(
+        "stats_speaker_map",
+        &[
+            "stats",
+            "meeting-roadmap",
+            "--speaker-map",
+            map.to_str().unwrap(),
+        ],
+    );
+    let json = stdout_json(&output);
+
+    assert_eq!(json["speakers"][0]["speaker"], "Me");
+    assert_eq!(json["speakers"][1]["speaker"], "Them");
+}
+
+#[test]
+fn test_transcript_srt_format() {
+    let output = run_with_fixture(
+        "transcript_srt",
+        &["transcript", "meeting-roadmap", "--format", "srt"],
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("1\n00:00:00,000 --> 00:00:04,000\nmicrophone: Welcome everyone.\n"));
+    // Cues are capped at 7 seconds when the next segment is far away
+    assert!(stdout.contains("00:01:15,000 --> 00:01:22,000"));
+}
+
+#[test]
+fn test_transcript_vtt_format() {
+    let output = run_with_fixture(
+        "transcript_vtt",
+        &[
+            "transcript",
+            "meeting-roadmap",
+            "--format",
+            "vtt",
+            "--map-speakers",
+        ],
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("WEBVTT\n"));
+    assert!(
+        stdout.contains("00:00:10.000 --> 00:00:17.000\n<v Bob Jones>Thanks, I have the numbers.")
+    );
+}
+
+#[test]
+fn test_search_text_format() {
+    let output = run_with_fixture("search_text", &["search", "roadmap", "--format", "text"]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("1 result(s) for \"roadmap\""));
+    assert!(stdout.contains("meeting-roadmap  2025-01-15 10:00  Roadmap Planning"));
+}
+
+#[test]
+fn test_transcript_markdown_format() {
+    let output = run_with_fixture(
+        "transcript_markdown",
+        &["transcript", "meeting-roadmap", "--format", "markdown"],
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("# Roadmap Planning\n"));
+    assert!(stdout.contains("**system** [01:15]: Revenue grew last quarter."));
+}
+
+#[test]
+fn test_unsupported_format_for_command() {
+    let output = run_with_fixture("search_srt", &["search", "roadmap", "--format", "srt"]);
+
+    assert_eq!(output.status.code(), Some(5));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("not supported by the search command"));
+}
+
+#[test]
+fn test_search_ndjson_format() {
+    let output = run_with_fixture("search_ndjson", &["search", "", "--format", "ndjson"]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["id"], "meeting-standup");
+    assert_eq!(lines[1]["id"], "meeting-roadmap");
+}
+
+#[test]
+fn test_transcript_ndjson_format() {
+    let output = run_with_fixture(
+        "transcript_ndjson",
+        &["transcript", "meeting-roadmap", "--format", "ndjson"],
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 7);
+    assert_eq!(
+        lines[0],
+        r#"{"s":"microphone","t":"Welcome everyone.","ts":0}"#
+    );
+}
+
+#[test]
+fn test_search_csv_format() {
+    let output = run_with_fixture(
+        "search_csv",
+        &[
+            "search",
+            "roadmap",
+            "--format",
+            "csv",
+            "--columns",
+            "id,summary,has_notes",
+        ],
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "id,summary,has_notes\r\nmeeting-roadmap,\"Quarterly roadmap review with the \"\"platform\"\" team, Q1.\",true\r\n"
+    );
+}
+
+#[test]
+fn test_search_csv_unknown_column() {
+    let output = run_with_fixture(
+        "search_csv_columns",
+        &[
+            "search",
+            "roadmap",
+            "--format",
+            "csv",
+            "--columns",
+            "id,nope",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(5));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unknown column 'nope'"));
+}
+
+#[test]
+fn test_fields_projection_compact() {
+    let output = run_with_fixture(
+        "search_fields",
+        &[
+            "search",
+            "roadmap",
+            "--fields",
+            "results.id,results.title",
+            "--compact",
+        ],
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout.trim_end(),
+        r#"{"results":[{"id":"meeting-roadmap","title":"Roadmap Planning"}]}"#
+    );
+}
+
+#[test]
+fn test_fields_rejected_for_text_format() {
+    let output = run_with_fixture(
+        "search_fields_text",
+        &[
+            "search",
+            "roadmap",
+            "--fields",
+            "results.id",
+            "--format",
+            "text",
+        ],
+    );
+
+    assert_eq!(output.status.code(), Some(5));
+}
+
+#[test]
+fn test_schema_command() {
+    let output = granola()
+        .args(["schema", "transcript"])
+        .output()
+        .expect("Failed to execute command");
+    let json = stdout_json(&output);
+
+    assert_eq!(json["type"], "object");
+    assert!(json["properties"]["segments"]["items"]["properties"]["ts"].is_object());
+}
+
+#[test]
+fn test_schema_command_all() {
+    let output = granola()
+        .args(["schema"])
+        .output()
+        .expect("Failed to execute command");
+    let json = stdout_json(&output);
+
+    for command in [
+        "search",
+        "details",
+        "transcript",
+        "stats",
+        "documents",
+        "config",
+        "export",
+        "watch",
+        "error",
+    ] {
+        assert!(json[command].is_object(), "missing schema for {}", command);
+    }
+}
+
+#[test]
+fn test_envelope_output() {
+    let output = run_with_fixture(
+        "details_envelope",
+        &["details", "meeting-roadmap", "--envelope"],
+    );
+    let json = stdout_json(&output);
+
+    assert_eq!(json["schema_version"], "1.2");
+    assert_eq!(json["command"], "details");
+    assert!(json["generated_at"].is_string());
+    assert!(json["cache"]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("details_envelope.json"));
+    assert!(json["cache"]["mtime"].is_string());
+    assert_eq!(json["data"]["id"], "meeting-roadmap");
+}
+
+#[test]
+fn test_readme_envelope_example_is_current() {
+    let output = run_with_fixture("readme_envelope", &["search", "roadmap", "--envelope"]);
+    let version = stdout_json(&output)["schema_version"].clone();
+
+    // The README example is written by hand, so check it against the binary
+    let readme =
+        std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md")).unwrap();
+    let example = format!("\"schema_version\": {},", version);
+    assert!(
+        readme.contains(&example),
+        "README envelope example should show {}",
+        version
+    );
+    assert!(readme.contains(&format!("| `{}` |", version.as_str().unwrap())));
+}
+
+#[test]
+fn test_older_schema_version() {
+    let output = run_with_fixture(
+        "transcript_schema_version",
+        &[
+            "transcript",
+            "meeting-roadmap",
+            "--merge-turns",
+            "--from",
+            "0:10",
+            "--schema-version",
+            "1.0",
+        ],
+    );
+    let json = stdout_json(&output);
+
+    assert!(json.get("window").is_none());
+    assert!(json["segments"][0].get("te").is_none());
+
+    let output = run_with_fixture(
+        "stats_schema_version",
+        &["stats", "meeting-roadmap", "--schema-version", "1.0"],
+    );
+    assert_eq!(output.status.code(), Some(5));
+}
+
+#[test]
+fn test_documents_from_structured_notes() {
+    let output = run_with_fixture(
+        "documents_structured",
+        &["documents", "meeting-standup", "--format", "markdown"],
+    );
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("## Updates\n\n- **Deploy** is *blocked*\n  1. Fix CI"));
+    assert!(stdout.contains("- [ ] TODO: Bob to read the [runbook](https://example.com/runbook)"));
+    assert!(stdout.contains("- [x] Alice to rotate keys"));
+
+    let output = run_with_fixture(
+        "documents_structured_plain",
+        &["documents", "meeting-standup"],
+    );
+    let json = stdout_json(&output);
+    let notes = &json["documents"][0];
+    assert_eq!(notes["format"], "plain");
+    assert!(notes["content"]
+        .as_str()
+        .unwrap()
+        .starts_with("Updates\n\n- Deploy is blocked"));
+}
+
+#[test]
+fn test_documents_notes_format() {
+    let output = run_with_fixture(
+        "documents_both",
+        &["documents", "meeting-roadmap", "--notes-format", "both"],
+    );
+    let json = stdout_json(&output);
+    let documents = json["documents"].as_array().unwrap();
+    assert_eq!(json["total_documents"], 3);
+    assert_eq!(documents[0]["id"], "meeting-roadmap-notes-plain");
+    assert_eq!(documents[0]["format"], "plain");
+    assert_eq!(documents[1]["id"], "meeting-roadmap-notes-markdown");
+    assert_eq!(documents[1]["format"], "markdown");
+    assert!(documents[1]["content"]
+        .as_str()
+        .unwrap()
+        .starts_with("# Decisions"));
+
+    let output = run_with_fixture(
+        "documents_best",
+        &["documents", "meeting-roadmap", "--notes-format", "best"],
+    );
+    let json = stdout_json(&output);
+    assert_eq!(json["documents"][0]["id"], "meeting-roadmap-notes");
+    assert_eq!(json["documents"][0]["format"], "markdown");
+}
+
+#[test]
+fn test_actions() {
+    let output = run_with_fixture("actions", &["actions"]);
+    let json = stdout_json(&output);
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(json["total_items"], 4);
+
+    // Newest meeting first; TODO prefix stripped and owner resolved to a participant
+    assert_eq!(items[0]["meeting_id"], "meeting-standup");
+    assert_eq!(
+        items[0]["text"],
+        "Bob to read the [runbook](https://example.com/runbook)"
+    );
+    assert_eq!(items[0]["assignee"], "Bob Jones");
+    assert_eq!(items[0]["completed"], false);
+
+    assert_eq!(items[2]["text"], "Alice to draft the launch plan");
+    assert_eq!(items[2]["assignee"], "Alice Smith");
+    assert_eq!(items[3]["completed"], true);
+    assert!(items[3].get("ts").is_none());
+}
+
+#[test]
+fn test_actions_non_ascii_assignee() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("actions_non_ascii.json");
+    let mut state = fixture_state();
+    // The Kelvin sign lowercases to a shorter ASCII 'k'
+    state["documents"]["meeting-roadmap"]["notes_markdown"] =
+        "# Next steps\n\n- [ ] \u{212A}\u{212A} to ship it\n- [ ] \u{212A}elvin to ship it\n- [ ] Zoë will review\n"
+            .into();
+    write_cache(&path, &state);
+
+    let output = granola()
+        .arg("--cache-path")
+        .arg(&path)
+        .arg("actions")
+        .output()
+        .expect("Failed to execute command");
+    let json = stdout_json(&output);
+    let assignee = |text: &str| {
+        json["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|item| item["text"] == text)
+            .map(|item| item["assignee"].clone())
+            .unwrap()
+    };
+    assert_eq!(assignee("\u{212A}\u{212A} to ship it"), "\u{212A}\u{212A}");
+    assert_eq!(assignee("\u{212A}elvin to ship it"), "\u{212A}elvin");
+    assert_eq!(assignee("Zoë will review"), "Zoë");
+}
+
+#[test]
+fn test_actions_colon_and_tags() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("actions_colon.json");
+    let mut state = fixture_state();
+    // A `#tag` line doesn't end the section, and "Label:" is only an owner
+    // when it names a participant
+    state["documents"]["meeting-roadmap"]["notes_markdown"] =
+        "# Next steps\n#launch\n- Bob: send the invoice\n- Budget: cut travel\n".into();
+    write_cache(&path, &state);
+
+    let output = granola()
+        .arg("--cache-path")
+        .arg(&path)
+        .arg("actions")
+        .output()
+        .expect("Failed to execute command");
+    let json = stdout_json(&output);
+    let items: Vec<_> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|item| item["meeting_id"] == "meeting-roadmap")
+        .collect();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["text"], "Bob: send the invoice");
+    assert_eq!(items[0]["assignee"], "Bob Jones");
+    assert_eq!(items[1]["text"], "Budget: cut travel");
+    assert!(items[1]["assignee"].is_null());
+}
+
+#[test]
+fn test_actions_filters() {
+    let output = run_with_fixture("actions_owner", &["actions", "--owner", "bob", "--open"]);
+    let json = stdout_json(&output);
+    assert_eq!(json["total_items"], 1);
+    assert_eq!(json["items"][0]["meeting_id"], "meeting-standup");
+
+    let output = run_with_fixture("actions_since", &["actions", "--since", "2025-01-16"]);
+    let json = stdout_json(&output);
+    assert_eq!(json["total_items"], 2);
+
+    let output = run_with_fixture("actions_bad_since", &["actions", "--since", "last week"]);
+    assert_eq!(output.status.code(), Some(5));
+}
+
+#[test]
+fn test_documents_outline() {
+    let output = run_with_fixture(
+        "documents_outline",
+        &["documents", "meeting-roadmap", "--outline"],
+    );
+    let json = stdout_json(&output);
+    let headings = json["headings"].as_array().unwrap();
+    assert_eq!(headings.len(), 2);
+    assert_eq!(headings[0]["title"], "Decisions");
+    assert_eq!(headings[1]["title"], "Next steps");
+    assert_eq!(headings[1]["children"][0]["title"], "Risks");
+    assert_eq!(headings[1]["children"][0]["level"], 2);
+
+    // Structured notes are outlined through their Markdown rendering
+    let output = run_with_fixture(
+        "documents_outline_structured",
+        &["documents", "meeting-standup", "--outline"],
+    );
+    let json = stdout_json(&output);
+    assert_eq!(json["headings"][1]["title"], "Action items");
+}
+
+#[test]
+fn test_documents_section() {
+    let output = run_with_fixture(
+        "documents_section",
+        &["documents", "meeting-roadmap", "--section", "next steps"],
+    );
+    let json = stdout_json(&output);
+    assert_eq!(json["total_documents"], 1);
+    let document = &json["documents"][0];
+    assert_eq!(document["title"], "Roadmap Planning - Next steps");
+    assert_eq!(document["format"], "markdown");
+    let content = document["content"].as_str().unwrap();
+    assert!(content.starts_with("- [ ] Alice to draft the launch plan"));
+    assert!(content.contains("## Risks"));
+    assert!(!content.contains("Ship v2"));
+
+    let output = run_with_fixture(
+        "documents_section_missing",
+        &["documents", "meeting-roadmap", "--section", "Budget"],
+    );
+    assert_eq!(output.status.code(), Some(4));
+}
+
+/// Minimal HTTP/1.0 GET returning the status code and JSON body.
+fn http_get(addr: &str, path: &str) -> (u16, serde_json::Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {} HTTP/1.0\r\nHost: {}\r\n\r\n", path, addr).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn test_serve() {
+    let cache = fixture_cache("serve");
+    let mut child = granola()
+        .arg("--cache-path")
+        .arg(&cache)
+        .args(["serve", "--addr", "127.0.0.1:0"])
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // The server reports the port it was given on stderr
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    stderr.read_line(&mut line).unwrap();
+    let addr = line
+        .trim()
+        .strip_prefix("Listening on http://")
+        .unwrap()
+        .to_string();
+
+    let (status, json) = http_get(&addr, "/search?q=standup");
+    assert_eq!(status, 200);
+    assert_eq!(json["results"][0]["id"], "meeting-standup");
+
+    let (status, json) = http_get(&addr, "/meetings/meeting-roadmap/transcript?from=10%3A00");
+    assert_eq!(status, 200);
+    assert_eq!(json["total_segments"], 2);
+
+    let (status, json) = http_get(
+        &addr,
+        "/meetings/meeting-roadmap/documents?section=Next+steps",
+    );
+    assert_eq!(status, 200);
+    assert_eq!(
+        json["documents"][0]["title"],
+        "Roadmap Planning - Next steps"
+    );
+
+    let (status, json) = http_get(&addr, "/meetings/missing");
+    assert_eq!(status, 404);
+    assert_eq!(json["error"]["type"], "meeting_not_found");
+
+    // Rewriting the cache is picked up on the next request
+    let contents = std::fs::read_to_string(&cache).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    std::fs::write(&cache, contents.replace("Daily Standup", "Team Standup")).unwrap();
+    let (_, json) = http_get(&addr, "/meetings/meeting-standup");
+    assert_eq!(json["title"], "Team Standup");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn test_mcp() {
+    let cache = fixture_cache("mcp");
+    let mut child = granola()
+        .arg("--cache-path")
+        .arg(&cache)
+        .arg("mcp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let requests = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-06-18"}}"#,
+        r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+        r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"search_meetings","arguments":{"query":"roadmap"}}}"#,
+        r#"{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{"name":"get_meeting_details","arguments":{"meeting_id":"missing"}}}"#,
+        r#"{"jsonrpc":"2.0","id":5,"method":"resources/read","params":{"uri":"granola://meetings/meeting-roadmap"}}"#,
+    ];
+    let mut stdin = child.stdin.take().unwrap();
+    for request in requests {
+        writeln!(stdin, "{}", request).unwrap();
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output().unwrap();
+    let responses: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    // The notification gets no response
+    assert_eq!(responses.len(), 5);
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2025-06-18");
+
+    let tools = responses[1]["result"]["tools"].as_array().unwrap();
+    let names: Vec<&str> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
+    assert_eq!(
+        names,
+        [
+            "search_meetings",
+            "get_meeting_details",
+            "get_transcript",
+            "get_documents"
+        ]
+    );
+    assert_eq!(tools[0]["inputSchema"]["required"][0], "query");
+
+    let result = &responses[2]["result"];
+    assert_eq!(result["isError"], false);
+    assert_eq!(
+        result["structuredContent"]["results"][0]["id"],
+        "meeting-roadmap"
+    );
+
+    assert_eq!(responses[3]["result"]["isError"], true);
+
+    let text = responses[4]["result"]["contents"][0]["text"]
+        .as_str()
+        .unwrap();
+    let details: serde_json::Value = serde_json::from_str(text).unwrap();
+    assert_eq!(details["title"], "Roadmap Planning");
+}
+
+#[test]
+fn test_tui_requires_terminal() {
+    // Test output is piped, so there is no terminal to draw on
+    let output = run_with_fixture("tui", &["tui"]);
+    assert_eq!(output.status.code(), Some(5));
+}
+
+#[test]
+fn test_completions() {
+    for shell in ["bash", "zsh", "fish"] {
+        let output = granola().args(["completions", shell]).output().unwrap();
+        assert!(output.status.success());
+        let script = String::from_utf8_lossy(&output.stdout);
+        assert!(script.contains("transcript"), "{} script", shell);
+        assert!(script.contains("granola-cli"), "{} binary name", shell);
+        assert!(
+            script.contains("GRANOLA_CACHE_PATH=$cache_path")
+                || script.contains("GRANOLA_CACHE_PATH=(string"),
+            "{} cache path",
+            shell
+        );
+        assert!(
+            script.contains("_GRANOLA_COMPLETE=meetings"),
+            "{} hook",
+            shell
+        );
+    }
+
+    // The hook lists meetings newest first as id<TAB>title
+    let output = granola()
+        .env("_GRANOLA_COMPLETE", "meetings")
+        .env("GRANOLA_CACHE_PATH", fixture_cache("completions"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "meeting-standup\tDaily Standup",
+            "meeting-roadmap\tRoadmap Planning"
+        ]
+    );
+}
+
+#[test]
+fn test_bash_completion_after_global_flags() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("bash_completion");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = granola().args(["completions", "bash"]).output().unwrap();
+    std::fs::write(dir.join("granola.bash"), &output.stdout).unwrap();
+
+    let cache = fixture_cache("bash_completion");
+    // The script registers and runs the binary under its installed name
+    let bin_dir = PathBuf::from(env!("CARGO_BIN_EXE_granola-cli"))
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let complete = |words: &str| {
+        let script = format!(
+            "source granola.bash; COMP_WORDS=({}); COMP_CWORD=$((${{#COMP_WORDS[@]}} - 1)); _granola_with_meetings; echo \"${{COMPREPLY[*]}}\"",
+            words
+        );
+        let output = isolate_env(&mut Command::new("bash"))
+            .current_dir(&dir)
+            .args(["-c", &script])
+            .env(
+                "PATH",
+                format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap()),
+            )
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    let cache = cache.display();
+    assert_eq!(
+        complete(&format!(
+            "granola-cli --format text --cache-path {} details ''",
+            cache
+        )),
+        "meeting-standup meeting-roadmap"
+    );
+    assert_eq!(
+        complete(&format!(
+            "granola-cli --cache-path = {} transcript meeting-r",
+            cache
+        )),
+        "meeting-roadmap"
+    );
+    // Only the first positional is a meeting ID, and flag values are not
+    for words in [
+        "transcript meeting-roadmap ''",
+        "transcript --from ''",
+        "transcript --from = ''",
+    ] {
+        let reply = complete(&format!("granola-cli --cache-path {} {}", cache, words));
+        assert!(!reply.contains("meeting-"), "{}: {}", words, reply);
+    }
+    assert_eq!(
+        complete(&format!(
+            "granola-cli --cache-path {} transcript --from 1:00 meeting-s",
+            cache
+        )),
+        "meeting-standup"
+    );
+}
+
+fn write_config(name: &str, contents: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{}.toml", name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_config_profiles() {
+    let cache = fixture_cache("config_profiles");
+    let config = write_config(
+        "config_profiles",
+        &format!(
+            "[default]\ncache_path = {:?}\nlimit = 1\n\n[profiles.work]\nlimit = 5\n",
+            cache.display().to_string()
+        ),
+    );
+    let search = |args: &[&str]| {
+        let output = granola()
+            .args(args)
+            .env("GRANOLA_CONFIG", &config)
+            .env_remove("GRANOLA_PROFILE")
+            .env_remove("GRANOLA_LIMIT")
+            .output()
+            .expect("Failed to execute command");
+        stdout_json(&output)["results"].as_array().unwrap().len()
+    };
+
+    // The cache path comes from the config file
+    assert_eq!(search(&["search", ""]), 1);
+    assert_eq!(search(&["--profile", "work", "search", ""]), 2);
+    // The command line beats the config file
+    assert_eq!(
+        search(&["--profile", "work", "search", "", "--limit", "1"]),
+        1
+    );
+}
+
+#[test]
+fn test_broken_config_only_affects_commands_that_read_it() {
+    let config = write_config("config_broken", "[default\nlimit = \"many\"\n");
+    let output = granola()
+        .args(["search", "roadmap"])
+        .env("GRANOLA_CONFIG", &config)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(5));
+
+    for args in [
+        &["workflow"][..],
+        &["schema", "details"],
+        &["completions", "bash"],
+    ] {
+        let output = granola()
+            .args(args)
+            .env("GRANOLA_CONFIG", &config)
+            .env("GRANOLA_LIMIT", "lots")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_config_unknown_profile() {
+    let config = write_config("config_unknown_profile", "[default]\nlimit = 1\n");
+    let output = granola()
+        .args(["--profile", "missing", "config", "show"])
+        .env("GRANOLA_CONFIG", &config)
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(5));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Profile 'missing' not found"));
+}
+
+#[test]
+fn test_config_show() {
+    let config = write_config(
+        "config_show",
+        "[default]\nlimit = 10\njson_errors = true\n\n[profiles.work]\nlimit = 20\n",
+    );
+    let output = granola()
+        .args(["--profile", "work", "config", "show"])
+        .args(["--cache-path", "/tmp/granola-cache.json"])
+        .env("GRANOLA_CONFIG", &config)
+        .output()
+        .expect("Failed to execute command");
+
+    let json = stdout_json(&output);
+    assert_eq!(json["config_file_exists"], true);
+    assert_eq!(json["profile"], "work");
+    let setting = |key: &str| {
+        json["settings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["key"] == key)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(setting("cache_path")["source"], "command line");
+    assert_eq!(setting("limit")["value"], 20);
+    assert_eq!(setting("limit")["source"], "config [profiles.work]");
+    assert_eq!(setting("json_errors")["source"], "config [default]");
+    assert_eq!(setting("format")["value"], "json");
+    assert_eq!(setting("format")["source"], "default");
+
+    let output = granola()
+        .args([
+            "--profile",
+            "work",
+            "--format",
+            "markdown",
+            "config",
+            "show",
+        ])
+        .env("GRANOLA_CONFIG", &config)
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    let markdown = String::from_utf8_lossy(&output.stdout);
+    assert!(markdown.contains("| `limit` | 20 | config [profiles.work] |"));
+}
+
+#[test]
+fn test_watch() {
+    let cache = fixture_cache("watch");
+    let mut child = granola()
+        .arg("--cache-path")
+        .arg(&cache)
+        .args(["watch", "--interval", "50", "--debounce", "50"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // Wait until the first load is done so the changes below are diffed
+    let mut stderr = BufReader::new(child.stderr.take().unwrap());
+    let mut line = String::new();
+    stderr.read_line(&mut line).unwrap();
+    assert!(line.starts_with("Watching "), "unexpected stderr: {}", line);
+
+    let mut state = fixture_state();
+    state["transcripts"]["meeting-roadmap"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({ "text": "One more thing.", "source": "microphone", "timestamp": 900 }));
+    let standup = &mut state["documents"]["meeting-standup"];
+    standup["updated_at"] = "2025-01-16T10:00:00Z".into();
+    standup["notes_plain"] = "Deploy is unblocked".into();
+    let mut retro = state["documents"]["meeting-standup"].clone();
+    retro["id"] = "meeting-retro".into();
+    retro["title"] = "Retro".into();
+    retro["updated_at"] = "2025-01-17T10:00:00Z".into();
+    state["documents"]["meeting-retro"] = retro;
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    write_cache(&cache, &state);
+
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let events: Vec<serde_json::Value> = (0..4)
+        .map(|_| {
+            let mut line = String::new();
+            stdout.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        })
+        .collect();
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    let summary: Vec<(&str, &str)> = events
+        .iter()
+        .map(|e| {
+            (
+                e["event"].as_str().unwrap(),
+                e["meeting_id"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("transcript_extended", "meeting-roadmap"),
+            ("meeting_updated", "meeting-standup"),
+            ("notes_changed", "meeting-standup"),
+            ("meeting_created", "meeting-retro"),
+        ]
+    );
+    assert_eq!(events[0]["transcript_segments"], 8);
+    assert_eq!(events[0]["previous_transcript_segments"], 7);
+    assert_eq!(events[3]["title"], "Retro");
+}
+
+/// Accept `responses.len()` requests, answering each with the next status,
+/// and return the raw requests.
+fn http_listener(responses: Vec<u16>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let handle = std::thread::spawn(move || {
+        responses
+            .into_iter()
+            .map(|status| {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+                request
+            })
+            .collect()
+    });
+    (addr, handle)
+}
+
+#[test]
+fn test_hooks() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("hooks");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let cache = dir.join("cache.json");
+    write_cache(&cache, &fixture_state());
+    let log = dir.join("hooks.log");
+    let payload = dir.join("payload.json");
+
+    // The webhook fails once, then succeeds on the retry
+    let (addr, listener) = http_listener(vec![500, 200]);
+    let config = write_config(
+        "hooks",
+        &format!(
+            r#"
+[[hooks.on_new_meeting]]
+name = "retros"
+command = 'echo "$GRANOLA_HOOK_EVENT $GRANOLA_MEETING_ID" >> {log}'
+title = "^Ret"
+
+[[hooks.on_new_meeting]]
+command = "cat > {payload}"
+participant = "ALICE"
+
+[[hooks.on_transcript_complete]]
+command = 'echo "$GRANOLA_HOOK_EVENT $GRANOLA_MEETING_ID" >> {log}'
+folder = "product"
+
+[[hooks.on_notes_updated]]
+url = "http://{addr}/hook"
+retry_delay_ms = 10
+"#,
+            log = log.display(),
+            payload = payload.display(),
+            addr = addr,
+        ),
+    );
+    let run_hooks = || {
+        let output = granola()
+            .arg("--cache-path")
+            .arg(&cache)
+            .args(["hooks", "run", "--once", "--transcript-idle", "0"])
+            .arg("--state-file")
+            .arg(dir.join("state.json"))
+            .env("GRANOLA_CONFIG", &config)
+            .output()
+            .expect("Failed to execute command");
+        assert!(
+            output.status.success(),
+            "hooks failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+
+    // The first run only records what is already there
+    run_hooks();
+    assert!(!log.exists());
+
+    let mut state = fixture_state();
+    state["transcripts"]["meeting-roadmap"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({ "text": "Bye.", "source": "microphone", "timestamp": 950 }));
+    state["documents"]["meeting-standup"]["notes_plain"] = "Deploy is unblocked".into();
+    let mut retro = state["documents"]["meeting-standup"].clone();
+    retro["id"] = "meeting-retro".into();
+    retro["title"] = "Retro".into();
+    state["documents"]["meeting-retro"] = retro;
+    let mut sync = state["documents"]["meeting-roadmap"].clone();
+    sync["id"] = "meeting-sync".into();
+    sync["title"] = "Sync".into();
+    state["documents"]["meeting-sync"] = sync;
+    write_cache(&cache, &state);
+
+    run_hooks();
+    assert_eq!(
+        std::fs::read_to_string(&log).unwrap(),
+        "on_new_meeting meeting-retro\non_transcript_complete meeting-roadmap\n"
+    );
+    let meeting: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&payload).unwrap()).unwrap();
+    assert_eq!(meeting["id"], "meeting-sync");
+    assert_eq!(meeting["title"], "Sync");
+
+    let requests = listener.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].starts_with("POST /hook "));
+    assert!(requests[1]
+        .to_lowercase()
+        .contains("x-granola-event: on_notes_updated"));
+    assert!(requests[1].contains(r#""id":"meeting-standup""#));
+
+    // Nothing fires twice
+    run_hooks();
+    assert_eq!(std::fs::read_to_string(&log).unwrap().lines().count(), 2);
+}
+
+#[test]
+fn test_hooks_command_timeout() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("hooks_timeout");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let cache = dir.join("cache.json");
+    write_cache(&cache, &fixture_state());
+    let log = dir.join("hooks.log");
+
+    // The first hook hangs; it must not hold up the second
+    let config = write_config(
+        "hooks_timeout",
+        &format!(
+            r#"
+[[hooks.on_new_meeting]]
+command = "sleep 60"
+timeout_secs = 1
+retries = 1
+retry_delay_ms = 10
+
+[[hooks.on_new_meeting]]
+command = 'echo "$GRANOLA_MEETING_ID" >> {log}'
+"#,
+            log = log.display(),
+        ),
+    );
+    let run_hooks = || {
+        granola()
+            .arg("--cache-path")
+            .arg(&cache)
+            .args(["hooks", "run", "--once"])
+            .arg("--state-file")
+            .arg(dir.join("state.json"))
+            .env("GRANOLA_CONFIG", &config)
+            .output()
+            .expect("Failed to execute command")
+    };
+    run_hooks();
+
+    let mut state = fixture_state();
+    let mut retro = state["documents"]["meeting-standup"].clone();
+    retro["id"] = "meeting-retro".into();
+    state["documents"]["meeting-retro"] = retro;
+    write_cache(&cache, &state);
+
+    let started = std::time::Instant::now();
+    let output = run_hooks();
+    assert!(output.status.success());
+    assert!(started.elapsed() < std::time::Duration::from_secs(20));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("timed out after 1s"), "{}", stderr);
+    assert!(stderr.contains("giving up after 2 attempts"), "{}", stderr);
+    assert_eq!(std::fs::read_to_string(&log).unwrap(), "meeting-retro\n");
+}
+
+#[test]
+fn test_hooks_failed_delivery_retried() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("hooks_failed");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let cache = dir.join("cache.json");
+    write_cache(&cache, &fixture_state());
+    let log = dir.join("hooks.log");
+    let ready = dir.join("ready");
+
+    // Fails until the `ready` file exists
+    let config = write_config(
+        "hooks_failed",
+        &format!(
+            r#"
+[[hooks.on_new_meeting]]
+command = 'test -f {ready} && echo "$GRANOLA_MEETING_ID" >> {log}'
+retries = 0
+"#,
+            ready = ready.display(),
+            log = log.display(),
+        ),
+    );
+    let run_hooks = || {
+        granola()
+            .arg("--cache-path")
+            .arg(&cache)
+            .args(["hooks", "run", "--once"])
+            .arg("--state-file")
+            .arg(dir.join("state.json"))
+            .env("GRANOLA_CONFIG", &config)
+            .output()
+            .expect("Failed to execute command")
+    };
+    run_hooks();
+
+    let mut state = fixture_state();
+    let mut retro = state["documents"]["meeting-standup"].clone();
+    retro["id"] = "meeting-retro".into();
+    state["documents"]["meeting-retro"] = retro;
+    write_cache(&cache, &state);
+
+    let output = run_hooks();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("giving up after 1 attempts"), "{}", stderr);
+    assert!(!log.exists());
+
+    // The failed delivery is kept and made on the next run, once
+    std::fs::write(&ready, "").unwrap();
+    run_hooks();
+    run_hooks();
+    assert_eq!(std::fs::read_to_string(&log).unwrap(), "meeting-retro\n");
+}
+
+#[test]
+fn test_export_markdown() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("export_markdown");
+    let _ = std::fs::remove_dir_all(&dir);
+    let cache = dir.join("cache.json");
+    std::fs::create_dir_all(&dir).unwrap();
+    write_cache(&cache, &fixture_state());
+    let out = dir.join("out");
+    let export = |args: &[&str]| {
+        let output = granola()
+            .arg("--cache-path")
+            .arg(&cache)
+            .args(["export", "--dir"])
+            .arg(&out)
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+        stdout_json(&output)
+    };
+
+    let json = export(&["--transcript", "--filename", "{year}/{date} {title}.md"]);
+    assert_eq!(json["total_meetings"], 2);
+    assert_eq!(json["written"], 2);
+    assert_eq!(
+        json["files"][0]["path"],
+        "2025/2025-01-15 Roadmap Planning.md"
+    );
+
+    let roadmap = std::fs::read_to_string(out.join("2025/2025-01-15 Roadmap Planning.md")).unwrap();
+    assert!(roadmap.starts_with(
+        "---\nid: \"meeting-roadmap\"\ntitle: \"Roadmap Planning\"\ndate: \"2025-01-15T10:00:00Z\"\nparticipants:\n  - \"Alice Smith\"\n  - \"Bob Jones\"\ntype: \"meeting\"\n"
+    ));
+    assert!(roadmap.contains("\n## Overview\n\nQuarterly roadmap review"));
+    // The notes' own headings sit below the Notes section
+    assert!(roadmap.contains("\n## Notes\n\n### Decisions\n"));
+    assert!(roadmap.contains("\n## Transcript\n\n**Alice Smith** [00:00]: Welcome everyone."));
+
+    // Filters
+    let json = export(&["--since", "2025-01-16", "--filename", "{id}.md"]);
+    assert_eq!(json["total_meetings"], 1);
+    assert_eq!(json["files"][0]["meeting_id"], "meeting-standup");
+    let json = export(&[
+        "--query",
+        "roadmap",
+        "--until",
+        "2025-01-16",
+        "--filename",
+        "{id}.md",
+    ]);
+    assert_eq!(json["files"][0]["meeting_id"], "meeting-roadmap");
+
+    // Incremental exports only rewrite meetings whose updated_at changed
+    let json = export(&["--incremental", "--filename", "{id}.md"]);
+    assert_eq!(json["unchanged"], 2);
+    let mut state = fixture_state();
+    state["documents"]["meeting-standup"]["updated_at"] = "2025-02-01T00:00:00Z".into();
+    write_cache(&cache, &state);
+    let json = export(&["--incremental", "--filename", "{id}.md"]);
+    assert_eq!(json["written"], 1);
+    assert_eq!(json["files"][1]["meeting_id"], "meeting-standup");
+    assert_eq!(json["files"][1]["status"], "written");
+
+    // ...or whose transcript grew, or that now need the transcript
+    let json = export(&["--incremental", "--transcript", "--filename", "{id}.md"]);
+    assert_eq!(json["written"], 2);
+    state["transcripts"]["meeting-roadmap"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({ "text": "Bye.", "source": "system", "timestamp": 950 }));
+    write_cache(&cache, &state);
+    let json = export(&["--incremental", "--transcript", "--filename", "{id}.md"]);
+    assert_eq!(json["written"], 1);
+    assert_eq!(json["files"][0]["status"], "written");
+    let roadmap = std::fs::read_to_string(out.join("meeting-roadmap.md")).unwrap();
+    assert!(roadmap.ends_with("**Bob Jones** [15:50]: Bye.\n"));
+
+    // A renamed meeting replaces its old file
+    state["documents"]["meeting-roadmap"]["title"] = "Roadmap Review".into();
+    write_cache(&cache, &state);
+    let json = export(&["--incremental"]);
+    assert_eq!(json["files"][0]["path"], "2025-01-15-roadmap-review.md");
+    let json = export(&["--incremental", "--filename", "{date} {title}.md"]);
+    assert_eq!(json["written"], 2);
+    assert_eq!(
+        json["removed_files"],
+        serde_json::json!([
+            "2025-01-15-roadmap-review.md",
+            "2025-01-16-daily-standup.md"
+        ])
+    );
+    assert!(out.join("2025-01-15 Roadmap Review.md").exists());
+    assert!(!out.join("2025-01-15-roadmap-review.md").exists());
+    assert!(!out.join("meeting-roadmap.md").exists());
+
+    let output = granola()
+        .arg("--cache-path")
+        .arg(&cache)
+        .args(["--format", "markdown", "export", "--dir"])
+        .arg(&out)
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("| `meeting-standup` | 2025-01-16-daily-standup.md | written |"));
+}
+
+#[test]
+fn test_export_file_name_collisions() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("export_collisions");
+    let _ = std::fs::remove_dir_all(&dir);
+    let cache = dir.join("cache.json");
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut state = fixture_state();
+    for id in ["meeting-roadmap", "meeting-standup"] {
+        state["documents"][id]["title"] = "...".into();
+    }
+    write_cache(&cache, &state);
+    let out = dir.join("out");
+    let export = |args: &[&str]| {
+        let output = granola()
+            .arg("--cache-path")
+            .arg(&cache)
+            .args(["export", "--dir"])
+            .arg(&out)
+            .args(["--filename", "{title}"])
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+        stdout_json(&output)
+    };
+
+    // Titles with nothing usable in a file name still get one
+    let json = export(&[]);
+    assert_eq!(json["files"][0]["path"], "untitled");
+    assert_eq!(json["files"][1]["path"], "untitled-meeting-standup");
+
+    // A filtered export doesn't take a name from a meeting it skipped
+    let json = export(&["--since", "2025-01-16"]);
+    assert_eq!(json["files"][0]["path"], "untitled-meeting-standup");
+    let roadmap = std::fs::read_to_string(out.join("untitled")).unwrap();
+    assert!(roadmap.contains("id: \"meeting-roadmap\""));
+}
+
+#[test]
+fn test_export_bad_filename_template() {
+    let output = run_with_fixture(
+        "export_bad_template",
+        &[
+            "export",
+            "--dir",
+            env!("CARGO_TARGET_TMPDIR"),
+            "--filename",
+            "{name}.md",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(5));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown placeholder '{name}'"));
+}
+
+#[test]
+fn test_export_obsidian() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("export_obsidian");
+    let _ = std::fs::remove_dir_all(&dir);
+    let output = run_with_fixture(
+        "export_obsidian",
+        &["export", "--obsidian", "--dir", dir.to_str().unwrap()],
+    );
+    let json = stdout_json(&output);
+    assert_eq!(
+        json["files"][0]["path"],
+        "Meetings/2025-01-15 Roadmap Planning.md"
+    );
+    assert_eq!(
+        json["people_files"],
+        serde_json::json!(["People/Alice Smith.md", "People/Bob Jones.md"])
+    );
+
+    let roadmap =
+        std::fs::read_to_string(dir.join("Meetings/2025-01-15 Roadmap Planning.md")).unwrap();
+    assert!(roadmap.contains("tags:\n  - \"type/meeting\"\n  - \"folder/product\"\n"));
+    assert!(
+        roadmap.contains("\nDate: [[2025-01-15]]\nParticipants: [[Alice Smith]], [[Bob Jones]]\n")
+    );
+
+    let bob = std::fs::read_to_string(dir.join("People/Bob Jones.md")).unwrap();
+    assert!(bob.contains("email: \"bob@example.com\""));
+    assert!(bob.contains(
+        "## Meetings\n\n<!-- granola:meetings -->\n- [[2025-01-15]] [[Meetings/2025-01-15 Roadmap Planning|Roadmap Planning]]\n- [[2025-01-16]] [[Meetings/2025-01-16 Daily Standup|Daily Standup]]\n<!-- /granola:meetings -->\n"
+    ));
+
+    // Person notes that didn't change are left alone
+    let output = run_with_fixture(
+        "export_obsidian",
+        &["export", "--obsidian", "--dir", dir.to_str().unwrap()],
+    );
+    assert!(stdout_json(&output).get("people_files").is_none());
+}
+
+#[test]
+fn test_export_obsidian_keeps_person_notes() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("export_obsidian_since");
+    let _ = std::fs::remove_dir_all(&dir);
+    let export = |since: &str| {
+        let output = run_with_fixture(
+            "export_obsidian_since",
+            &[
+                "export",
+                "--obsidian",
+                "--dir",
+                dir.to_str().unwrap(),
+                "--since",
+                since,
+            ],
+        );
+        stdout_json(&output)
+    };
+    let bob = dir.join("People/Bob Jones.md");
+
+    export("2025-01-16");
+    let note = std::fs::read_to_string(&bob).unwrap();
+    assert!(!note.contains("Roadmap Planning"));
+    std::fs::write(
+        &bob,
+        note.replace("# Bob Jones\n", "# Bob Jones\n\nRuns the numbers.\n")
+            + "\nMet at the offsite.\n",
+    )
+    .unwrap();
+
+    // An export of other meetings adds links without dropping earlier ones
+    // or the user's own text
+    let json = export("2025-01-15");
+    assert_eq!(
+        json["people_files"],
+        serde_json::json!(["People/Alice Smith.md", "People/Bob Jones.md"])
+    );
+    let note = std::fs::read_to_string(&bob).unwrap();
+    assert!(note.contains("# Bob Jones\n\nRuns the numbers.\n"));
+    assert!(note.contains(
+        "<!-- granola:meetings -->\n- [[2025-01-15]] [[Meetings/2025-01-15 Roadmap Planning|Roadmap Planning]]\n- [[2025-01-16]] [[Meetings/2025-01-16 Daily Standup|Daily Standup]]\n<!-- /granola:meetings -->\n"
+    ));
+    assert!(note.ends_with("\nMet at the offsite.\n"));
+
+    export("2025-01-16");
+    assert_eq!(std::fs::read_to_string(&bob).unwrap(), note);
+}
+
+#[test]
+fn test_export_sqlite() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("export_sqlite");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let cache = dir.join("cache.json");
+    let db = dir.join("meetings.db");
+    let mut state = fixture_state();
+    write_cache(&cache, &state);
+    let export = |args: &[&str]| {
+        let output = granola()
+            .arg("--cache-path")
+            .arg(&cache)
+            .args(["export", "--sqlite"])
+            .arg(&db)
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+        stdout_json(&output)
+    };
+
+    let json = export(&[]);
+    assert_eq!(json["written"], 2);
+    assert!(json["files"][0].get("path").is_none());
+
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+    assert_eq!(count("SELECT count(*) FROM meetings"), 2);
+    assert_eq!(count("SELECT count(*) FROM people"), 2);
+    assert_eq!(
+        count("SELECT count(*) FROM meeting_people WHERE meeting_id = 'meeting-roadmap'"),
+        3
+    );
+    assert_eq!(count("SELECT count(*) FROM notes"), 2);
+    let (speaker, source, ts): (String, String, i64) = conn
+        .query_row(
+            "SELECT speaker, source, ts FROM segments WHERE meeting_id = 'meeting-roadmap' AND position = 2",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!(
+        (speaker.as_str(), source.as_str(), ts),
+        ("Bob Jones", "system", 10)
+    );
+    let hit: String = conn
+        .query_row(
+            "SELECT meeting_id FROM meetings_fts WHERE meetings_fts MATCH 'runbook'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(hit, "meeting-standup");
+    drop(conn);
+
+    let json = export(&["--incremental"]);
+    assert_eq!(json["written"], 0);
+    assert_eq!(json["unchanged"], 2);
+
+    // An edited meeting is rewritten in place, without duplicating rows
+    state["documents"]["meeting-standup"]["updated_at"] = "2025-01-17T09:00:00Z".into();
+    state["documents"]["meeting-standup"]["title"] = "Standup (renamed)".into();
+    write_cache(&cache, &state);
+    let json = export(&["--incremental"]);
+    assert_eq!(json["written"], 1);
+    assert_eq!(json["files"][1]["meeting_id"], "meeting-standup");
+    assert_eq!(json["files"][1]["status"], "written");
+
+    let conn = rusqlite::Connection::open(&db).unwrap();
+    let title: String = conn
+        .query_row(
+            "SELECT title FROM meetings WHERE id = 'meeting-standup'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(title, "Standup (renamed)");
+    let titles: i64 = conn
+        .query_row(
+            "SELECT count(*) FROM meetings_fts WHERE kind = 'title'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(titles, 2);
+    // The old title is gone from the index, not just from the table
+    let stale: i64 = conn
+        .query_row(
+            "SELECT count(*) FROM meetings_fts WHERE meetings_fts MATCH 'daily' AND kind = 'title'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(stale, 0);
+    let renamed: String = conn
+        .query_row(
+            "SELECT meeting_id FROM meetings_fts WHERE meetings_fts MATCH 'renamed'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(renamed, "meeting-standup");
+}
+
+#[test]
+fn test_transcript_time_overflow() {
+    let output = run_with_fixture(
+        "transcript_time_overflow",
+        &[
+            "transcript",
+            "meeting-roadmap",
+            "--from",
+            "999999999999999999:00:00",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(5));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid time"));
+}